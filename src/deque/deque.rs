@@ -1,20 +1,26 @@
 
+// 环形缓冲区实现的双端队列，两端添加和删除都是 O(1)
+// head 是队尾元素的下标，从队尾到队首依次是 data[head], data[head + 1], ...，超过 cap 时回到 0
 #[derive(Debug)]
 pub struct Deque<T> {
     cap: usize,
-    data: Vec<T>,
+    data: Vec<Option<T>>,
+    head: usize,
+    len: usize,
 }
 
 impl<T> Deque<T> {
     pub fn new(cap: usize) -> Self {
         Self {
             cap,
-            data: Vec::with_capacity(cap),
+            data: (0..cap).map(|_| None).collect(),
+            head: 0,
+            len: 0,
         }
     }
 
     pub fn len(&self) -> usize {
-        self.data.len()
+        self.len
     }
 
     pub fn is_empty(&self) -> bool {
@@ -26,25 +32,29 @@ impl<T> Deque<T> {
     }
 
     pub fn clear(&mut self) {
-        self.data = Vec::with_capacity(self.cap);
+        *self = Self::new(self.cap);
     }
 
-    // Vec 的末尾为队首
+    // 队首在 head + len - 1 的位置
     pub fn add_front(&mut self, val: T) -> Result<(), String> {
         if self.is_full() {
             return Err("No space available".to_string());
         }
-        self.data.push(val);
+        let i = (self.head + self.len) % self.cap;
+        self.data[i] = Some(val);
+        self.len += 1;
 
         Ok(())
     }
 
-    // Vec 的首部为队尾
+    // 队尾在 head 的位置，head 往前移一位
     pub fn add_rear(&mut self, val: T) -> Result<(), String> {
         if self.is_full() {
             return Err("No space available".to_string())
         }
-        self.data.insert(0, val);
+        self.head = (self.head + self.cap - 1) % self.cap;
+        self.data[self.head] = Some(val);
+        self.len += 1;
 
         Ok(())
     }
@@ -52,7 +62,9 @@ impl<T> Deque<T> {
     // 从队首移除数据
     pub fn remove_front(&mut self) -> Option<T> {
         if self.len() > 0 {
-            return self.data.pop();
+            self.len -= 1;
+            let i = (self.head + self.len) % self.cap;
+            return self.data[i].take();
         }
         None
     }
@@ -60,7 +72,10 @@ impl<T> Deque<T> {
     // 从队尾移除数据
     pub fn remove_rear(&mut self) -> Option<T> {
         if self.len() > 0 {
-            return Some(self.data.remove(0));
+            let val = self.data[self.head].take();
+            self.head = (self.head + 1) % self.cap;
+            self.len -= 1;
+            return val;
         }
         None
     }
@@ -68,7 +83,7 @@ impl<T> Deque<T> {
     pub fn into_iter(self) -> IntoIter<T> {
         // IntoIter(self)
         IntoIter {
-            deque: Deque { cap: self.cap, data: self.data }
+            deque: self
         }
     }
 
@@ -79,9 +94,10 @@ impl<T> Deque<T> {
         // for item in self.data.iter() {
         //     iterator.data.push(item);
         // }
+        // 没有元素的位置都是 None，从 head 开始绕一圈就是从队尾到队首的顺序
+        let (before, after) = self.data.split_at(self.head);
         let mut iterator = Iter {
-            // data: self.data.iter().map(|item| item).collect()
-            data: self.data.iter().collect()
+            data: after.iter().chain(before.iter()).filter_map(|item| item.as_ref()).collect()
         };
         iterator
     }
@@ -90,7 +106,8 @@ impl<T> Deque<T> {
         let mut iterator = IterMut {
             data: vec![]
         };
-        for item in self.data.iter_mut() {
+        let (before, after) = self.data.split_at_mut(self.head);
+        for item in after.iter_mut().chain(before.iter_mut()).filter_map(|item| item.as_mut()) {
             iterator.data.push(item);
         }
         iterator
@@ -115,10 +132,7 @@ impl<T> Iterator for IntoIter<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if !self.deque.is_empty() {
-            return Some(self.deque.data.remove(0));
-        }
-        None
+        self.deque.remove_rear()
     }
 }
 
@@ -204,4 +218,24 @@ mod tests {
         println!("{} + {} = {}", sum1, addend, sum2);
         assert_eq!(14, d.into_iter().sum::<i32>());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn deque_ring_test() {
+        let mut d = Deque::new(3);
+        d.add_front(1).unwrap();
+        d.add_rear(0).unwrap();
+        d.add_front(2).unwrap();
+        assert!(d.add_rear(-1).is_err());
+        assert_eq!(d.iter().copied().collect::<Vec<_>>(), vec![0, 1, 2]);
+
+        // 反复从一端进、另一端出，下标会绕回数组的开头
+        for i in 3..10 {
+            assert_eq!(d.remove_rear(), Some(i - 3));
+            d.add_front(i).unwrap();
+            assert_eq!(d.len(), 3);
+        }
+        assert_eq!(d.iter().copied().collect::<Vec<_>>(), vec![7, 8, 9]);
+        assert_eq!(d.remove_front(), Some(9));
+        assert_eq!(d.into_iter().collect::<Vec<_>>(), vec![7, 8]);
+    }
+}
@@ -0,0 +1,178 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::NegativeWeight, Result};
+use crate::graph::index_min_pq::IndexMinPQ;
+use crate::graph::weighted_graph::WeightedGraph;
+
+// A* 搜索：单点对的最短路径，堆中按 dis[v] + h(v) 排序，h(v) 估计 v 到终点的距离
+// h 必须是可采纳的(不超过真实距离)，这样终点出队时得到的就是最短路径；h 恒为 0 时就是 Dijkstra
+#[derive(Debug, Clone)]
+pub struct AStar<'a> {
+    graph: &'a WeightedGraph,
+    source: usize,
+    target: usize,
+    pre: Vec<i32>,
+    dis: Vec<f64>,
+    found: bool,
+    // 出队扩展过的顶点数，用来比较不同的 h
+    expanded: usize,
+}
+
+impl<'a> AStar<'a> {
+    // 图中有负权边时返回 NegativeWeight
+    pub fn new<F: Fn(usize) -> f64>(graph: &'a WeightedGraph, source: usize, target: usize, heuristic: F) -> Result<Self> {
+        graph.validate_vertex(source)?;
+        graph.validate_vertex(target)?;
+
+        for v in 0..graph.v() {
+            for (w, weight) in graph.adj_weighted(v) {
+                if weight < 0.0 {
+                    return Err(NegativeWeight(v, w, weight));
+                }
+            }
+        }
+
+        let v_size = graph.v();
+        let mut a_star = Self {
+            graph,
+            source,
+            target,
+            pre: vec![-1; v_size],
+            dis: vec![f64::INFINITY; v_size],
+            found: false,
+            expanded: 0,
+        };
+        a_star.search(heuristic);
+        Ok(a_star)
+    }
+
+    fn search<F: Fn(usize) -> f64>(&mut self, heuristic: F) {
+        let g = self.graph;
+
+        let mut pq = IndexMinPQ::new(g.v());
+        self.dis[self.source] = 0.0;
+        self.pre[self.source] = self.source as i32;
+        pq.push(self.source, heuristic(self.source));
+
+        while let Some((v, _)) = pq.pop() {
+            self.expanded += 1;
+            if v == self.target {
+                self.found = true;
+                return;
+            }
+
+            // h 只是可采纳而不一致时，已经出队的顶点也可能被更新，这时重新入堆
+            for (w, weight) in g.adj_weighted(v) {
+                if self.dis[v] + weight < self.dis[w] {
+                    self.dis[w] = self.dis[v] + weight;
+                    self.pre[w] = v as i32;
+                    pq.push(w, self.dis[w] + heuristic(w));
+                }
+            }
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.found
+    }
+
+    // 起点到终点的最短距离，不可达时是无穷大
+    pub fn dis(&self) -> f64 {
+        if self.found {
+            self.dis[self.target]
+        } else {
+            f64::INFINITY
+        }
+    }
+
+    pub fn path(&self) -> Vec<usize> {
+        let mut res = vec![];
+        if !self.found {
+            return res;
+        }
+
+        let mut cur = self.target;
+        while cur != self.source {
+            res.push(cur);
+            cur = self.pre[cur] as usize;
+        }
+        res.push(self.source);
+
+        res.reverse();
+        res
+    }
+
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dijkstra::Dijkstra;
+    use crate::graph::error::MatrixError;
+    use crate::graph::graph_builder::GraphBuilder;
+    use crate::graph::test_util::grid_edges;
+
+    // n x n 的网格，第 n / 2 列除了最后一行都是墙，每走一步花费 1
+    fn grid(n: usize) -> WeightedGraph {
+        let mut graph = WeightedGraph::with_vertices(n * n);
+        let wall = |v: usize| v % n == n / 2 && v / n < n - 1;
+        for (v, w) in grid_edges(n).filter(|&(v, w)| !wall(v) && !wall(w)) {
+            graph.add_weighted_edge(v, w, 1.0).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn a_star_test() {
+        let n = 30;
+        let graph = grid(n);
+        let (s, t) = (0, n - 1);
+        let manhattan = |v: usize| ((v / n).abs_diff(t / n) + (v % n).abs_diff(t % n)) as f64;
+
+        let dijkstra = Dijkstra::new(&graph, s).unwrap();
+
+        let zero = AStar::new(&graph, s, t, |_| 0.0).unwrap();
+        let a_star = AStar::new(&graph, s, t, manhattan).unwrap();
+
+        // 要绕过墙，先走到最后一行再回到第一行
+        assert_eq!(a_star.dis(), dijkstra.dis(t));
+        assert_eq!(a_star.dis(), (3 * (n - 1)) as f64);
+        assert_eq!(zero.dis(), a_star.dis());
+        assert!(a_star.expanded() < zero.expanded());
+
+        let path = a_star.path();
+        assert_eq!((path[0], *path.last().unwrap()), (s, t));
+        assert_eq!(path.len() as f64, a_star.dis() + 1.0);
+        assert!(path.windows(2).all(|pair| graph.weight(pair[0], pair[1]).is_some()));
+    }
+
+    #[test]
+    fn weighted_test() {
+        let graph = WeightedGraph::from_file("g_weighted.txt");
+        let a_star = AStar::new(&graph, 0, 3, |_| 0.0).unwrap();
+        assert_eq!(a_star.dis(), 5.0);
+        assert_eq!(a_star.path(), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn unreachable_test() {
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("4 3\n0 1 1.5\n1 2 2\n3 0 1").unwrap();
+
+        let a_star = AStar::new(&graph, 0, 3, |_| 0.0).unwrap();
+        assert!(!a_star.is_connected());
+        assert_eq!(a_star.dis(), f64::INFINITY);
+        assert!(a_star.path().is_empty());
+        assert_eq!(a_star.expanded(), 3);
+    }
+
+    #[test]
+    fn negative_weight_test() {
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("2 1\n0 1 -1").unwrap();
+        assert!(matches!(AStar::new(&graph, 0, 1, |_| 0.0), Err(MatrixError::NegativeWeight(0, 1, _))));
+    }
+}
@@ -1,208 +1,229 @@
-// use std::fs::File;
-// use std::{fs, io, usize};
-// use std::collections::{BTreeSet, HashMap};
-// use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
-// use std::path::Path;
-// use std::str::FromStr;
-// use crate::graph::error::{MatrixError::*, MatrixError, Result};
-//
-// trait Iterable {
-//     type Item;
-//     type Iter<'a>: Iterator<Item = &'a Self::Item> where Self: 'a;
-//     fn iter(&self) -> Self::Iter<'_>;
-// }
-//
-// trait MutablyIterable: Iterable {
-//     type IterMut<'a>: Iterator<Item = &'a mut Self::Item> where Self: 'a;
-//     fn iter_mut(&mut self) -> Self::IterMut<'_>;
-// }
-// trait Collection: MutablyIterable + FromIterator<Item = Self::Item> + IntoIterator<Item = Self::Item> {
-//     fn is_empty(&self) -> bool {
-//         self.len() == 0
-//     }
-//     fn len(&self) -> usize;
-//     fn push_back(&mut self, v: Self::Item);
-//     fn pop_back(&mut self) -> Option<T>;
-// }
-//
-//
-// #[derive(Debug, Clone)]
-// pub struct AdjIter {
-//     pub v: usize,
-//     pub e: usize,
-//     pub adj: Vec<Collection>,
-// }
-//
-// impl AdjIter {
-//     // 读取文件
-//     fn new(file_path: &str) -> Self {
-//         let mut v = 0;
-//         let mut e = 0;
-//         if let Ok(content) = AdjIter::read_file(file_path) {
-//             if let Ok((vertex, edge)) = AdjIter::read_v_e(&content) {
-//                 v = vertex;
-//                 e = edge;
-//             }
-//         };
-//
-//         Self {
-//             v,
-//             e,
-//             adj: Default::default()
-//         }
-//     }
-//
-//     fn init_matrix(&mut self) -> Option<Vec<Collection>> {
-//         if let Ok(content) = AdjIter::read_file("g.txt") {
-//             AdjIter::read_v_e(&content)
-//                 .map(|(v, e)| {
-//                     let linklist_vec: Vec<Collection> = (0..v).map(|_| Collection::Set(BTreeSet::new())).collect();
-//                     self.read_second2end(&content, linklist_vec).unwrap_or_default()
-//                 })
-//                 .and_then(|adj| self.print_adj(adj.clone()).map(|_| adj))
-//                 .map(|adj| {
-//                     println!("print success!");
-//                     self.adj = adj.clone();
-//                     adj
-//                 }).ok()
-//         } else {
-//             eprintln!("Error reading file");
-//             None
-//         }
-//     }
-//
-//
-//     // 校验读到的数据，从第二行开始，第一列不能大于顶点数
-//     fn validate_vertex(&self, v: usize) -> Result<()> {
-//         // v是第一行读到的总顶点数，num1是从第二行开始读到的顶点编号
-//         if v < 0 || v >= self.v {
-//             return Err(InvalidVertexEdge(v, self.v));
-//         }
-//         Ok(())
-//     }
-//
-//     fn read_second2end(&self, content: &String, mut adj: Vec<Collection>) -> Result<Vec<Collection>> {
-//         let mut num1 = 0;
-//         let mut num2 = 0;
-//         // 从第二行开始读取，并赋值给二维数组
-//         let mut s = content.lines().skip(1);
-//         while let Some(line) = s.next() {
-//             let mut iter = line.split_whitespace();
-//             num1 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-//             num2 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-//
-//             // 遇到平行边(将要处理的边在前面已经存储过了就是平行边)
-//             if adj[num1].contains(&num2) {
-//                 return Err(ParallelEdge);
-//             }
-//
-//
-//             // 遇到自环边
-//             if num1 == num2 {
-//                 return Err(SelfLoop);
-//             }
-//
-//             if let Ok(_) = self.validate_vertex(num1) {
-//                 adj[num1].insert(num2);
-//                 adj[num2].insert(num1);
-//             }
-//         }
-//         Ok(adj)
-//     }
-//
-//
-//     fn read_file(file_path: &str) -> Result<String> {
-//         let contents = fs::read_to_string(file_path)
-//             // .map_err(|e| e.into())
-//             // .and_then(|s| Ok::<String, io::Error>(s.trim().to_owned()))?;
-//             .map_err(|_| ReadFileError)
-//             .and_then(|s| Ok::<String, _>(s.trim().to_owned()))?;
-//
-//         Ok(contents)
-//     }
-//
-//     // 只读取第一行，(顶点，边数)
-//     fn read_v_e<S: AsRef<str>>(s: S) -> Result<(usize, usize)> {
-//         let s = s.as_ref();
-//         let mut v = 0;
-//         let mut e = 0;
-//
-//         if let Some(line) = s.lines().next() {
-//             let mut iter = line.split_whitespace();
-//             v = i32::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-//             e = i32::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-//         }
-//
-//         if v <= 0 {
-//             return Err(VertexError);
-//         }
-//
-//         Ok((v as usize, e as usize))
-//     }
-//
-//     fn print_adj(&self, mut adj: Vec<Collection>) -> Result<()> {
-//         if adj.len() == 0 {
-//             return Err(PrintMatrixError);
-//         }
-//
-//         let mut buffer = io::BufWriter::new(io::stdout());
-//
-//         // 顶点使用v, w这些名称，索引用i, j会更加清晰
-//         for v in 0..adj.len() {
-//             writeln!(buffer, "vertex:{v} --> {:?} ", adj[v].iter().map(|w| w)).map_err(|_| PrintMatrixError)?;
-//         }
-//
-//         buffer.flush().map_err(|_| PrintMatrixError)?;
-//
-//         Ok(())
-//     }
-//
-//     // 传入两个顶点
-//     fn has_edge(&self, v: usize, w: usize) -> bool {
-//         // 对传入的顶点校验
-//         let _ = self.validate_vertex(v);
-//         let _ = self.validate_vertex(w);
-//
-//         self.adj[v].contains(&w)
-//     }
-//
-//     // 返回和某个顶点相连的边(只要找到相邻的点，就找到对应的边)，即返回相连的顶点的集合
-//     fn adj(&self, v: usize) -> &Collection {
-//         // 校验v是否合法
-//         let _ = self.validate_vertex(v);
-//
-//         // 直接返回顶点v对应的链表
-//         return &self.adj[v]
-//     }
-//
-//     // 顶点的度(顶点的邻边的个数)
-//     fn degree(&self, v: usize) -> usize {
-//         self.adj(v).len()
-//     }
-// }
-//
-//
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
-//
-//     #[test]
-//     fn basic_test() {
-//         let mut adj_list = AdjIter::new("g.txt");
-//         let al = adj_list.init_matrix().unwrap();
-//         println!("--------");
-//         // println!("{:?}", al.v);
-//         // println!("{:?}", al.e);
-//         println!("{:?}", al);
-//     }
-// }
-
-use crate::graph::error::{MatrixError::*, MatrixError, Result};
+use std::collections::BTreeSet;
+use std::fs;
+use std::str::FromStr;
+use crate::graph::error::{MatrixError, MatrixError::*, Result};
 
+// 读文件得到的边 (v, w, 权值)，没有第三列时权值默认为 1
+pub type Edges = Vec<(usize, usize, f64)>;
+
+// 所有图的底层表示(邻接矩阵、邻接表、邻接集合)都实现这个 trait，算法只依赖这个 trait，不关心底层存储
 pub trait AdjIterable {
-    fn validate_vertex(&self, v: usize) -> Result<()>;
+    // 遍历某个顶点的相邻顶点的迭代器，邻接矩阵不能返回引用，所以统一按值返回顶点编号
+    type Iter<'a>: Iterator<Item = usize> where Self: 'a;
+
+    // 顶点数
+    fn v(&self) -> usize;
+
+    // 边数
+    fn e(&self) -> usize;
 
+    // 返回和顶点 v 相邻的顶点
+    fn adj(&self, v: usize) -> Self::Iter<'_>;
+
+    // 传入两个顶点，判断是否有边
     fn has_edge(&self, v: usize, w: usize) -> bool;
 
-    fn read_v_e<S: AsRef<str>>(s: S) -> Result<(usize, usize)>;
+    // 是否是有向图
+    fn is_directed(&self) -> bool;
+
+    // 由已经校验过的边构建图，各个底层表示只需要实现这一个方法，读文件和解析文本都由下面的默认方法完成
+    fn from_edge_list(v: usize, e: usize, edges: Edges, directed: bool) -> Self where Self: Sized;
+
+    // 从文件构建无向图，读取失败时得到一个空图，需要错误信息时用 try_from_file
+    fn from_file(file_path: &str) -> Self where Self: Sized {
+        Self::try_from_file(file_path).unwrap_or_else(|_| Self::from_edge_list(0, 0, vec![], false))
+    }
+
+    // 从文件构建有向图，每一行 v w 表示一条 v -> w 的边
+    fn from_file_directed(file_path: &str) -> Self where Self: Sized {
+        Self::try_from_file_directed(file_path).unwrap_or_else(|_| Self::from_edge_list(0, 0, vec![], true))
+    }
+
+    // 从 "V E" 开头的文本构建图，和 from_file 不同，读取出错时返回错误
+    fn from_data<S: AsRef<str>>(s: S, directed: bool) -> Result<Self> where Self: Sized {
+        let (v, e, edges) = Self::read_edges(s, directed)?;
+        Ok(Self::from_edge_list(v, e, edges, directed))
+    }
+
+    // 宽松模式构建图，同时返回读取时跳过的行的警告
+    fn from_data_lenient<S: AsRef<str>>(s: S, directed: bool) -> Result<(Self, Vec<MatrixError>)> where Self: Sized {
+        let (v, e, edges, warnings) = Self::read_edges_lenient(s, directed)?;
+        Ok((Self::from_edge_list(v, e, edges, directed), warnings))
+    }
+
+    // 读取文件替换当前的图，有向还是无向保持不变
+    fn init_matrix(&mut self, file_path: &str) -> Result<()> where Self: Sized {
+        self.read_data(Self::read_file(file_path)?)
+    }
+
+    // 用文本的内容替换当前的图，读取出错时图保持不变
+    fn read_data<S: AsRef<str>>(&mut self, s: S) -> Result<()> where Self: Sized {
+        *self = Self::from_data(s, self.is_directed())?;
+        Ok(())
+    }
+
+    // 宽松模式读取，跳过有问题的行，返回收集到的警告
+    fn read_data_lenient<S: AsRef<str>>(&mut self, s: S) -> Result<Vec<MatrixError>> where Self: Sized {
+        let (graph, warnings) = Self::from_data_lenient(s, self.is_directed())?;
+        *self = graph;
+        Ok(warnings)
+    }
+
+    // 从文件构建无向图，读取出错时返回错误，不会像 from_file 一样得到一个空图
+    fn try_from_file(file_path: &str) -> Result<Self> where Self: Sized {
+        Self::from_data(Self::read_file(file_path)?, false)
+    }
+
+    fn try_from_file_directed(file_path: &str) -> Result<Self> where Self: Sized {
+        Self::from_data(Self::read_file(file_path)?, true)
+    }
+
+    // 反图：把所有边的方向反过来，无向图的反图就是它自己
+    fn reverse(&self) -> Self where Self: Sized;
+
+    // 顶点的度(顶点的邻边的个数)，有向图中就是出度
+    fn degree(&self, v: usize) -> usize {
+        self.adj(v).count()
+    }
+
+    // 出度：从 v 出发的边数
+    fn out_degree(&self, v: usize) -> usize {
+        self.degree(v)
+    }
+
+    // 入度：指向 v 的边数，无向图中入度和出度都等于度
+    fn in_degree(&self, v: usize) -> usize {
+        if !self.is_directed() {
+            return self.degree(v);
+        }
+
+        let _ = self.validate_vertex(v);
+        (0..self.v()).filter(|&u| self.has_edge(u, v)).count()
+    }
+
+    // 校验读到的数据，从第二行开始，第一列不能大于顶点数
+    fn validate_vertex(&self, v: usize) -> Result<()> {
+        if v >= self.v() {
+            return Err(InvalidVertexEdge(v, self.v()));
+        }
+        Ok(())
+    }
+
+    fn read_file(file_path: &str) -> Result<String> where Self: Sized {
+        let contents = fs::read_to_string(file_path)
+            .map_err(|_| ReadFileError)
+            .map(|s| s.trim().to_owned())?;
+
+        Ok(contents)
+    }
+
+    // 只读取第一行，(顶点，边数)
+    fn read_v_e<S: AsRef<str>>(s: S) -> Result<(usize, usize)> where Self: Sized {
+        let line = s.as_ref().lines().next().unwrap_or_default();
+        let mut iter = line.split_whitespace();
+        let v = parse_number(iter.next(), 1, line)?;
+        let e = parse_number(iter.next(), 1, line)?;
+
+        if v == 0 {
+            return Err(VertexError);
+        }
+
+        Ok((v, e))
+    }
+
+    // 读取整个文件内容，返回 (顶点数，边数，边的列表)，各个底层表示再把边存到自己的结构里
+    // 有向图中 v w 和 w v 是两条不同的边，不算平行边
+    // 第三列是可选的权值，整数或者小数都可以，不带权的表示直接忽略它
+    // 严格模式：任何一行有问题，或者边数和第一行声明的不一致，都直接返回带行号的错误
+    fn read_edges<S: AsRef<str>>(s: S, directed: bool) -> Result<(usize, usize, Edges)> where Self: Sized {
+        let (v, e) = Self::read_v_e(&s)?;
+        let edges = parse_edges(s.as_ref(), v, e, directed, None)?;
+        Ok((v, e, edges))
+    }
+
+    // 宽松模式：跳过有问题的行，把错误收集起来作为警告返回，只有第一行出错时才返回错误
+    // 返回的边数是实际读到的边数
+    fn read_edges_lenient<S: AsRef<str>>(s: S, directed: bool) -> Result<(usize, usize, Edges, Vec<MatrixError>)> where Self: Sized {
+        let (v, e) = Self::read_v_e(&s)?;
+        let mut warnings = vec![];
+        let edges = parse_edges(s.as_ref(), v, e, directed, Some(&mut warnings))?;
+        Ok((v, edges.len(), edges, warnings))
+    }
+}
+
+// 解析一列非负整数，缺少这一列或者不是数字时返回带行号的错误
+fn parse_number(token: Option<&str>, line_no: usize, line: &str) -> Result<usize> {
+    let token = token.ok_or_else(|| MissingColumn(line_no, line.to_owned()))?;
+    usize::from_str(token).map_err(|_| InvalidNumber(line_no, token.to_owned()))
+}
+
+// 从第二行开始解析每一条边，warnings 为 None 时是严格模式，遇到错误直接返回
+fn parse_edges(s: &str, v: usize, e: usize, directed: bool, mut warnings: Option<&mut Vec<MatrixError>>) -> Result<Edges> {
+    let mut edges = Vec::new();
+    // 记录已经读到的边，用来检测平行边。无向图较小的顶点在前
+    let mut seen = BTreeSet::new();
+
+    // 从第二行开始读取，行号从 1 开始数，空行直接跳过
+    for (line_no, line) in s.lines().enumerate().skip(1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match parse_edge(line, line_no + 1, v, directed, &mut seen) {
+            Ok(edge) => edges.push(edge),
+            Err(err) => match warnings.as_mut() {
+                Some(warnings) => warnings.push(err),
+                None => return Err(err),
+            },
+        }
+    }
+
+    if edges.len() != e {
+        let err = EdgeCountMismatch(e, edges.len());
+        match warnings {
+            Some(warnings) => warnings.push(err),
+            None => return Err(err),
+        }
+    }
+
+    Ok(edges)
+}
+
+fn parse_edge(line: &str, line_no: usize, v: usize, directed: bool, seen: &mut BTreeSet<(usize, usize)>) -> Result<(usize, usize, f64)> {
+    let mut iter = line.split_whitespace();
+    let num1 = parse_number(iter.next(), line_no, line)?;
+    let num2 = parse_number(iter.next(), line_no, line)?;
+
+    let weight = match iter.next() {
+        None => 1.0,
+        Some(token) => match f64::from_str(token) {
+            Ok(weight) if weight.is_finite() => weight,
+            _ => return Err(InvalidWeight(line_no, token.to_owned())),
+        },
+    };
+
+    // 最多三列，多出来的列不能默默丢掉
+    if iter.next().is_some() {
+        return Err(ExtraColumn(line_no, line.to_owned()));
+    }
+
+    // 两个顶点都必须小于顶点数
+    for num in [num1, num2] {
+        if num >= v {
+            return Err(VertexOutOfRange(line_no, num, v));
+        }
+    }
+
+    // 遇到自环边
+    if num1 == num2 {
+        return Err(SelfLoop(line_no));
+    }
+
+    // 遇到平行边(将要处理的边在前面已经存储过了就是平行边)
+    let key = if directed { (num1, num2) } else { (num1.min(num2), num1.max(num2)) };
+    if !seen.insert(key) {
+        return Err(ParallelEdge(line_no));
+    }
+
+    Ok((num1, num2, weight))
 }
\ No newline at end of file
@@ -1,202 +1,223 @@
-use std::fs::File;
-use std::{fs, io, usize};
-use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
-use std::path::Path;
-use std::str::FromStr;
-use crate::graph::error::{MatrixError::*, MatrixError, Result};
-
-#[derive(Debug)]
+use std::io;
+use std::io::Write;
+use crate::graph::adj_iterable::{AdjIterable, Edges};
+use crate::graph::graph_builder::GraphBuilder;
+use crate::graph::error::{MatrixError::*, Result};
+
+#[derive(Debug, Clone)]
 pub struct AdjMatrix {
     pub v: usize,
     pub e: usize,
     pub adj: Vec<Vec<usize>>,     // 邻接矩阵
+    pub directed: bool,
 }
 
 impl AdjMatrix {
-    // 读取文件
-    fn new(file_path: &str) -> Self {
-        let mut v = 0;
-        let mut e = 0;
-        if let Ok(content) = AdjMatrix::read_file(file_path) {
-            if let Ok((vertex, edge)) = AdjMatrix::read_v_e(&content) {
-                v = vertex;
-                e = edge;
-            }
-        };
-
+    pub fn new() -> Self {
         Self {
-            v,
-            e,
+            v: 0,
+            e: 0,
             adj: vec![],
+            directed: false,
         }
     }
 
-    fn init_matrix(&mut self) -> Option<Vec<Vec<usize>>> {
-
-        if let Ok(content) = AdjMatrix::read_file("g.txt") {
-            AdjMatrix::read_v_e(&content)
-                // .map(|(v, e)| (v, e, self.read_second2end(&content, vec![vec![0; v]; v]).unwrap_or_default()))
-                .map(|(v, e)| self.read_second2end(&content, vec![vec![0; v]; v]).unwrap_or_default())
-                // print_adj 返回 Result<()>，然后用直接接上 map 处理，map 把调用 self.print_adj 前的数据 (v, e, adj) 返回出去
-                // .and_then(|(_, _, adj)| self.print_adj(adj.clone()).map(|_| adj))
-                .and_then(|adj| self.print_adj(adj.clone()).map(|_| adj))
-                .map(|adj| {
-                    println!("print success!");
-                    self.adj = adj.clone();
-                    adj
-                }).ok()
-        } else {
-            eprintln!("Error reading file");
-            None
+    // 有向图
+    pub fn new_directed() -> Self {
+        Self {
+            directed: true,
+            ..AdjMatrix::new()
         }
     }
 
+    pub fn print_adj(&self, adj: Vec<Vec<usize>>) -> Result<()> {
+        if adj.is_empty() {
+            return Err(PrintMatrixError);
+        }
 
-    // 校验读到的数据，从第二行开始，第一列不能大于顶点数
-    fn validate_vertex(&self, v: usize) -> Result<()> {
-        // v是第一行读到的总顶点数，num1是从第二行开始读到的顶点编号
-        if v < 0 || v >= self.v {
-            return Err(InvalidVertexEdge(v, self.v));
+        let mut buffer = io::BufWriter::new(io::stdout());
+
+        for row in &adj {
+            for x in row {
+                // 当前的代码通过push_str拼接字符串,可以直接使用write!宏和循环来打印,避免创建String和拼接
+                // 因为前面也是io错误，所以如果错误类型是PrintMatrixError(io::Error)，那么可以写成map_err(PrintMatrixError)
+                write!(buffer, "{} ", x).map_err(|_| PrintMatrixError)?;
+            }
+            writeln!(buffer).map_err(|_| PrintMatrixError)?;
         }
-        Ok(())
-    }
 
-    fn read_second2end(&self, content: &String, mut adj: Vec<Vec<usize>>) -> Result<Vec<Vec<usize>>> {
-        let mut num1 = 0;
-        let mut num2 = 0;
-        // 从第二行开始读取，并赋值给二维数组
-        let mut s = content.lines().skip(1);
-        while let Some(line) = s.next() {
-            let mut iter = line.split_whitespace();
-            num1 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-            num2 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
+        buffer.flush().map_err(|_| PrintMatrixError)?;
 
-            let (v, _) = AdjMatrix::read_v_e(&content).unwrap();
+        Ok(())
+    }
+}
 
-            // 遇到平行边(将要处理的边在前面已经存储过了就是平行边)
-            if adj[num1][num2] == 1 {
-                return Err(ParallelEdge);
-            }
+// 遍历邻接矩阵的一行，值为 1 的列就是相邻的顶点
+pub struct MatrixAdjIter<'a> {
+    row: &'a [usize],
+    cur: usize,
+}
 
-            // 遇到自环边
-            if num1 == num2 {
-                return Err(SelfLoop);
-            }
+impl<'a> Iterator for MatrixAdjIter<'a> {
+    type Item = usize;
 
-            if let Ok(_) = self.validate_vertex(num1) {
-                adj[num1][num2] = 1;
-                adj[num2][num1] = 1;
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.cur < self.row.len() {
+            let w = self.cur;
+            self.cur += 1;
+            if self.row[w] == 1 {
+                return Some(w);
             }
         }
-        Ok(adj)
+        None
     }
+}
 
+impl AdjIterable for AdjMatrix {
+    type Iter<'a> = MatrixAdjIter<'a>;
 
-    fn read_file(file_path: &str) -> Result<String> {
-        // let file = File::open(file_path)?;
-        // let mut reader = BufReader::new(file);
-        // let mut content = String::new();
-        // reader.read_to_string(&mut content)?;
-        // Ok(content)
-
-        let contents = fs::read_to_string(file_path)
-            // .map_err(|e| e.into())
-            // .and_then(|s| Ok::<String, io::Error>(s.trim().to_owned()))?;
-            .map_err(|_| ReadFileError)
-            .and_then(|s| Ok::<String, _>(s.trim().to_owned()))?;
-
-        // .or_else(|e| Err(e));    用 ? 代替 or_else
+    fn v(&self) -> usize {
+        self.v
+    }
 
+    fn e(&self) -> usize {
+        self.e
+    }
 
-        Ok(contents)
+    fn is_directed(&self) -> bool {
+        self.directed
     }
 
-    // 只读取第一行，(顶点，边数)
-    fn read_v_e<S: AsRef<str>>(s: S) -> Result<(usize, usize)> {
-        let s = s.as_ref();
-        let mut v = 0;
-        let mut e = 0;
+    // 返回和某个顶点相连的边(只要找到相邻的点，就找到对应的边)，即返回相连的顶点的集合
+    fn adj(&self, v: usize) -> Self::Iter<'_> {
+        // 校验v是否合法
+        let _ = self.validate_vertex(v);
 
-        if let Some(line) = s.lines().next() {
-            let mut iter = line.split_whitespace();
-            v = i32::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-            e = i32::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-        }
+        MatrixAdjIter { row: &self.adj[v], cur: 0 }
+    }
 
-        if v <= 0 {
-            return Err(VertexError);
-        }
+    // 传入两个顶点
+    fn has_edge(&self, v: usize, w: usize) -> bool {
+        // 对传入的顶点校验
+        let _ = self.validate_vertex(v);
+        let _ = self.validate_vertex(w);
 
-        Ok((v as usize, e as usize))
+        self.adj[v][w] == 1
     }
 
-    fn print_adj(&self, adj: Vec<Vec<usize>>) -> Result<()> {
-        if adj.len() == 0 {
-            return Err(PrintMatrixError);
+    // 把读到的边存到自己的结构里
+    fn from_edge_list(v: usize, e: usize, edges: Edges, directed: bool) -> Self {
+        let mut adj = vec![vec![0; v]; v];
+        for (num1, num2, _) in edges {
+            adj[num1][num2] = 1;
+            // 无向图两个方向都要存
+            if !directed {
+                adj[num2][num1] = 1;
+            }
         }
 
-        let mut buffer = io::BufWriter::new(io::stdout());
+        Self {
+            v,
+            e,
+            adj,
+            directed,
+        }
+    }
 
-        for i in 0..adj.len() {
-            for j in 0..adj.len() {
-                // Error test
-                // if i == 1 {
-                //     let err = io::Error::new(io::ErrorKind::Other, "oh, error comes");
-                //     return Err(PrintMatrixError(err));
-                // }
-                // 当前的代码通过push_str拼接字符串,可以直接使用write!宏和循环来打印,避免创建String和拼接
-                // 因为前面也是io错误，所以如果错误类型是PrintMatrixError(io::Error)，那么可以写成map_err(PrintMatrixError)
-                write!(buffer, "{} ", adj[i][j]).map_err(|_| PrintMatrixError)?;
+    // 邻接矩阵的反图就是转置矩阵
+    fn reverse(&self) -> Self {
+        let mut adj = vec![vec![0; self.v]; self.v];
+        for (v, row) in self.adj.iter().enumerate() {
+            for (w, &x) in row.iter().enumerate() {
+                adj[w][v] = x;
             }
-            writeln!(buffer).map_err(|_| PrintMatrixError)?;
         }
 
-        buffer.flush().map_err(|_| PrintMatrixError)?;
-
-        Ok(())
+        Self {
+            v: self.v,
+            e: self.e,
+            adj,
+            directed: self.directed,
+        }
     }
+}
 
-    // 传入两个顶点
-    fn has_edge(&self, v: usize, w: usize) -> bool {
-        // 对传入的顶点校验
-        let _ = self.validate_vertex(v);
 
-        self.adj[v][w] == 1
+impl GraphBuilder for AdjMatrix {
+    fn with_vertices(n: usize) -> Self {
+        Self {
+            v: n,
+            adj: vec![vec![0; n]; n],
+            ..AdjMatrix::new()
+        }
     }
 
-    // 返回和某个顶点相连的边(只要找到相邻的点，就找到对应的边)，即返回相连的顶点的集合
-    fn adj(&self, v: usize) -> Vec<usize> {
-        // 校验v是否合法
-        let _ = self.validate_vertex(v);
+    fn with_vertices_directed(n: usize) -> Self {
+        Self {
+            directed: true,
+            ..AdjMatrix::with_vertices(n)
+        }
+    }
 
-        let mut res = Vec::new();
-        for i in 0..self.v {
-            if self.adj[v][i] == 1 {
-                res.push(i);
-            }
+    // 每一行多一列，再多一行
+    fn add_vertex(&mut self) -> usize {
+        for row in self.adj.iter_mut() {
+            row.push(0);
         }
+        self.v += 1;
+        self.adj.push(vec![0; self.v]);
+        self.v - 1
+    }
 
-        res
+    fn insert_edge(&mut self, v: usize, w: usize) {
+        self.adj[v][w] = 1;
+        if !self.directed {
+            self.adj[w][v] = 1;
+        }
+        self.e += 1;
     }
 
-    // 顶点的度(顶点的邻边的个数)
-    fn degree(&self, v: usize) -> usize {
-        self.adj(v).len()
+    fn remove_edge(&mut self, v: usize, w: usize) -> bool {
+        if v >= self.v || w >= self.v || !self.has_edge(v, w) {
+            return false;
+        }
+        self.adj[v][w] = 0;
+        if !self.directed {
+            self.adj[w][v] = 0;
+        }
+        self.e -= 1;
+        true
     }
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn basic_test() {
-        let mut adj_matrix = AdjMatrix::new("g.txt");
-        let m = adj_matrix.init_matrix();
+        let adj_matrix = AdjMatrix::from_file("g.txt");
         println!("--------");
         println!("{:?}", adj_matrix.v);
         println!("{:?}", adj_matrix.e);
-        println!("{:?}", m.unwrap());
+        let _ = adj_matrix.print_adj(adj_matrix.adj.clone());
+
+        assert_eq!(adj_matrix.adj(2).collect::<Vec<_>>(), vec![0, 3, 6]);
+        assert_eq!(adj_matrix.degree(1), 3);
+        assert!(adj_matrix.has_edge(2, 6));
+        assert!(!adj_matrix.has_edge(0, 6));
+    }
+
+    #[test]
+    fn directed_test() {
+        let matrix = AdjMatrix::from_file_directed("g_directed.txt");
+
+        assert_eq!(matrix.adj(3).collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(matrix.in_degree(4), 2);
+        assert_eq!(matrix.out_degree(4), 0);
+
+        let rev = matrix.reverse();
+        assert_eq!(rev.adj(4).collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(rev.out_degree(0), 0);
     }
 }
\ No newline at end of file
@@ -1,166 +1,175 @@
-use std::fs::File;
-use std::{fs, io, usize};
-use std::collections::BTreeSet;
-use std::io::{BufRead, BufReader, ErrorKind, Read, Write};
-use std::path::Path;
-use std::str::FromStr;
-use crate::graph::error::{MatrixError::*, MatrixError, Result};
-
-#[derive(Debug)]
+use std::collections::{btree_set, BTreeSet};
+use std::io;
+use std::io::Write;
+use std::iter::Copied;
+use crate::graph::adj_iterable::{AdjIterable, Edges};
+use crate::graph::graph_builder::GraphBuilder;
+use crate::graph::error::{MatrixError::*, Result};
+
+#[derive(Debug, Clone)]
 pub struct AdjSet {
     pub v: usize,
     pub e: usize,
-    pub adj: Vec<BTreeSet<usize>>,
+    pub adj: Vec<BTreeSet<usize>>,     // 集合数组
+    pub directed: bool,
 }
 
 impl AdjSet {
-    // 读取文件
-    fn new(file_path: &str) -> Self {
-        let mut v = 0;
-        let mut e = 0;
-        if let Ok(content) = AdjSet::read_file(file_path) {
-            if let Ok((vertex, edge)) = AdjSet::read_v_e(&content) {
-                v = vertex;
-                e = edge;
-            }
-        };
-
+    pub fn new() -> Self {
         Self {
-            v,
-            e,
-            adj: Default::default()
+            v: 0,
+            e: 0,
+            adj: Default::default(),
+            directed: false,
         }
     }
 
-    fn init_matrix(&mut self) -> Option<Vec<BTreeSet<usize>>> {
-        if let Ok(content) = AdjSet::read_file("g.txt") {
-            AdjSet::read_v_e(&content)
-                .map(|(v, e)| {
-                    let linklist_vec: Vec<BTreeSet<usize>> = (0..v).map(|_| BTreeSet::new()).collect();
-                    self.read_second2end(&content, linklist_vec).unwrap_or_default()
-                })
-                .and_then(|adj| self.print_adj(adj.clone()).map(|_| adj))
-                .map(|adj| {
-                    println!("print success!");
-                    self.adj = adj.clone();
-                    adj
-                }).ok()
-        } else {
-            eprintln!("Error reading file");
-            None
+    // 有向图
+    pub fn new_directed() -> Self {
+        Self {
+            directed: true,
+            ..AdjSet::new()
         }
     }
 
+    pub fn print_adj(&self, adj: Vec<BTreeSet<usize>>) -> Result<()> {
+        if adj.is_empty() {
+            return Err(PrintMatrixError);
+        }
 
-    // 校验读到的数据，从第二行开始，第一列不能大于顶点数
-    fn validate_vertex(&self, v: usize) -> Result<()> {
-        // v是第一行读到的总顶点数，num1是从第二行开始读到的顶点编号
-        if v < 0 || v >= self.v {
-            return Err(InvalidVertexEdge(v, self.v));
+        let mut buffer = io::BufWriter::new(io::stdout());
+
+        // 顶点使用v, w这些名称，索引用i, j会更加清晰
+        for (v, list) in adj.iter().enumerate() {
+            writeln!(buffer, "vertex:{v} --> {:?} ", list).map_err(|_| PrintMatrixError)?;
         }
-        Ok(())
-    }
 
-    fn read_second2end(&self, content: &String, mut adj: Vec<BTreeSet<usize>>) -> Result<Vec<BTreeSet<usize>>> {
-        let mut num1 = 0;
-        let mut num2 = 0;
-        // 从第二行开始读取，并赋值给二维数组
-        let mut s = content.lines().skip(1);
-        while let Some(line) = s.next() {
-            let mut iter = line.split_whitespace();
-            num1 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-            num2 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
+        buffer.flush().map_err(|_| PrintMatrixError)?;
 
-            // 遇到平行边(将要处理的边在前面已经存储过了就是平行边)
-            if adj[num1].contains(&num2) {
-                return Err(ParallelEdge);
-            }
+        Ok(())
+    }
+}
 
+impl AdjIterable for AdjSet {
+    type Iter<'a> = Copied<btree_set::Iter<'a, usize>>;
 
-            // 遇到自环边
-            if num1 == num2 {
-                return Err(SelfLoop);
-            }
+    fn v(&self) -> usize {
+        self.v
+    }
 
-            if let Ok(_) = self.validate_vertex(num1) {
-                adj[num1].insert(num2);
-                adj[num2].insert(num1);
-            }
-        }
-        Ok(adj)
+    fn e(&self) -> usize {
+        self.e
     }
 
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
 
-    fn read_file(file_path: &str) -> Result<String> {
-        let contents = fs::read_to_string(file_path)
-            // .map_err(|e| e.into())
-            // .and_then(|s| Ok::<String, io::Error>(s.trim().to_owned()))?;
-            .map_err(|_| ReadFileError)
-            .and_then(|s| Ok::<String, _>(s.trim().to_owned()))?;
+    // 返回和某个顶点相连的边(只要找到相邻的点，就找到对应的边)，即返回相连的顶点的集合
+    fn adj(&self, v: usize) -> Self::Iter<'_> {
+        // 校验v是否合法
+        let _ = self.validate_vertex(v);
 
-        Ok(contents)
+        self.adj[v].iter().copied()
     }
 
-    // 只读取第一行，(顶点，边数)
-    fn read_v_e<S: AsRef<str>>(s: S) -> Result<(usize, usize)> {
-        let s = s.as_ref();
-        let mut v = 0;
-        let mut e = 0;
+    // 传入两个顶点
+    fn has_edge(&self, v: usize, w: usize) -> bool {
+        // 对传入的顶点校验
+        let _ = self.validate_vertex(v);
+        let _ = self.validate_vertex(w);
 
-        if let Some(line) = s.lines().next() {
-            let mut iter = line.split_whitespace();
-            v = i32::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-            e = i32::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-        }
+        self.adj[v].contains(&w)
+    }
 
-        if v <= 0 {
-            return Err(VertexError);
+    // 把读到的边存到自己的结构里
+    fn from_edge_list(v: usize, e: usize, edges: Edges, directed: bool) -> Self {
+        let mut adj: Vec<BTreeSet<usize>> = (0..v).map(|_| BTreeSet::new()).collect();
+        for (num1, num2, _) in edges {
+            adj[num1].insert(num2);
+            // 无向图两个方向都要存
+            if !directed {
+                adj[num2].insert(num1);
+            }
         }
 
-        Ok((v as usize, e as usize))
+        Self {
+            v,
+            e,
+            adj,
+            directed,
+        }
     }
 
-    fn print_adj(&self, mut adj: Vec<BTreeSet<usize>>) -> Result<()> {
-        if adj.len() == 0 {
-            return Err(PrintMatrixError);
+    // 把每条边 v -> w 存成 w -> v
+    fn reverse(&self) -> Self {
+        if !self.directed {
+            return self.clone();
         }
 
-        let mut buffer = io::BufWriter::new(io::stdout());
-
-        // 顶点使用v, w这些名称，索引用i, j会更加清晰
-        for v in 0..adj.len() {
-            writeln!(buffer, "vertex:{v} --> {:?} ", adj[v].iter().map(|w| w)).map_err(|_| PrintMatrixError)?;
+        let mut adj: Vec<BTreeSet<usize>> = (0..self.v).map(|_| BTreeSet::new()).collect();
+        for (v, list) in self.adj.iter().enumerate() {
+            for &w in list {
+                adj[w].insert(v);
+            }
         }
 
-        buffer.flush().map_err(|_| PrintMatrixError)?;
+        Self {
+            v: self.v,
+            e: self.e,
+            adj,
+            directed: self.directed,
+        }
+    }
 
-        Ok(())
+    // 顶点的度(顶点的邻边的个数)
+    fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
     }
+}
 
-    // 传入两个顶点
-    fn has_edge(&self, v: usize, w: usize) -> bool {
-        // 对传入的顶点校验
-        let _ = self.validate_vertex(v);
-        let _ = self.validate_vertex(w);
 
-        self.adj[v].contains(&w)
+impl GraphBuilder for AdjSet {
+    fn with_vertices(n: usize) -> Self {
+        Self {
+            v: n,
+            adj: (0..n).map(|_| BTreeSet::new()).collect(),
+            ..AdjSet::new()
+        }
     }
 
-    // 返回和某个顶点相连的边(只要找到相邻的点，就找到对应的边)，即返回相连的顶点的集合
-    fn adj(&self, v: usize) -> &BTreeSet<usize> {
-        // 校验v是否合法
-        let _ = self.validate_vertex(v);
+    fn with_vertices_directed(n: usize) -> Self {
+        Self {
+            directed: true,
+            ..AdjSet::with_vertices(n)
+        }
+    }
 
-        // 直接返回顶点v对应的链表
-        return &self.adj[v]
+    fn add_vertex(&mut self) -> usize {
+        self.adj.push(BTreeSet::new());
+        self.v += 1;
+        self.v - 1
     }
 
-    // 顶点的度(顶点的邻边的个数)
-    fn degree(&self, v: usize) -> usize {
-        self.adj(v).len()
+    fn insert_edge(&mut self, v: usize, w: usize) {
+        self.adj[v].insert(w);
+        if !self.directed {
+            self.adj[w].insert(v);
+        }
+        self.e += 1;
     }
-}
 
+    fn remove_edge(&mut self, v: usize, w: usize) -> bool {
+        if v >= self.v || w >= self.v || !self.adj[v].remove(&w) {
+            return false;
+        }
+        if !self.directed {
+            self.adj[w].remove(&v);
+        }
+        self.e -= 1;
+        true
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -168,11 +177,14 @@ mod tests {
 
     #[test]
     fn basic_test() {
-        let mut adj_list = AdjSet::new("g.txt");
-        let al = adj_list.init_matrix().unwrap();
+        let graph = AdjSet::from_file("g.txt");
         println!("--------");
-        // println!("{:?}", al.v);
-        // println!("{:?}", al.e);
-        println!("{:?}", al);
+        let _ = graph.print_adj(graph.adj.clone());
+
+        assert_eq!(graph.v(), 7);
+        assert_eq!(graph.e(), 6);
+        assert_eq!(graph.degree(2), 3);
+        assert!(graph.has_edge(3, 1));
+        assert!(!graph.has_edge(0, 6));
     }
-}
\ No newline at end of file
+}
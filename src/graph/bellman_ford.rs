@@ -0,0 +1,225 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::Result;
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 带权图的单源最短路径，可以处理负权边，并且能找出从源点可达的负权环。只借用图，构造的时候就完成计算
+#[derive(Debug, Clone)]
+pub struct BellmanFord<'a> {
+    graph: &'a WeightedGraph,
+    pre: Vec<i32>,
+    source: usize,
+    dis: Vec<f64>,
+    // 第 V 轮还能松弛就有负权环，不依赖是否找到了具体的环
+    has_negative_cycle: bool,
+    // 找到的负权环，按边的方向排列
+    negative_cycle: Option<Vec<usize>>,
+}
+
+impl<'a> BellmanFord<'a> {
+    pub fn new(graph: &'a WeightedGraph, source: usize) -> Result<Self> {
+        graph.validate_vertex(source)?;
+        let v_size = graph.v();
+
+        let mut bf = Self {
+            graph,
+            pre: vec![-1; v_size],
+            source,
+            dis: vec![f64::INFINITY; v_size],
+            has_negative_cycle: false,
+            negative_cycle: None,
+        };
+        bf.process();
+        Ok(bf)
+    }
+
+    fn process(&mut self) {
+        let g = self.graph;
+        let (pre, dis) = (&mut self.pre, &mut self.dis);
+
+        dis[self.source] = 0.0;
+        pre[self.source] = self.source as i32;
+
+        // 对所有边做 V-1 轮松弛操作，没有负权环时最短路径最多经过 V-1 条边
+        for _ in 1..g.v() {
+            let mut updated = false;
+            for v in 0..g.v() {
+                if dis[v] == f64::INFINITY {
+                    continue;
+                }
+                for (w, weight) in g.adj_weighted(v) {
+                    if dis[v] + weight < dis[w] {
+                        dis[w] = dis[v] + weight;
+                        pre[w] = v as i32;
+                        updated = true;
+                    }
+                }
+            }
+
+            // 这一轮没有更新，说明已经是最短路径了
+            if !updated {
+                return;
+            }
+        }
+
+        // 再做一轮，还能松弛说明有负权环
+        let mut last = None;
+        for v in 0..g.v() {
+            if dis[v] == f64::INFINITY {
+                continue;
+            }
+            for (w, weight) in g.adj_weighted(v) {
+                if dis[v] + weight < dis[w] {
+                    dis[w] = dis[v] + weight;
+                    pre[w] = v as i32;
+                    last = Some(w);
+                }
+            }
+        }
+
+        // 最后一个被松弛的顶点沿着 pre 往回走 V 步，一定已经走到环上了，再从这里找出环
+        if let Some(mut w) = last {
+            self.has_negative_cycle = true;
+            for _ in 0..g.v() {
+                match pre[w] {
+                    p if p < 0 => break,
+                    p => w = p as usize,
+                }
+            }
+            self.negative_cycle = find_cycle(pre, w);
+        }
+    }
+
+    pub fn has_negative_cycle(&self) -> bool {
+        self.has_negative_cycle
+    }
+
+    // 负权环上的顶点，例如 [1, 2, 3] 表示 1 -> 2 -> 3 -> 1
+    pub fn negative_cycle(&self) -> Option<&[usize]> {
+        self.negative_cycle.as_deref()
+    }
+
+    pub fn is_connected_to(&self, t: usize) -> bool {
+        let _ = self.graph.validate_vertex(t);
+        self.dis[t] != f64::INFINITY
+    }
+
+    // 从源点到 t 的最短距离，有负权环时没有意义
+    pub fn dis(&self, t: usize) -> f64 {
+        let _ = self.graph.validate_vertex(t);
+        self.dis[t]
+    }
+
+    // 整个距离数组
+    pub fn dis_to(&self) -> &[f64] {
+        &self.dis
+    }
+
+    pub fn pre(&self) -> &[i32] {
+        &self.pre
+    }
+
+    // 有负权环时最短路径不存在，返回空
+    pub fn path(&self, t: usize) -> Vec<usize> {
+        let mut res = vec![];
+        if self.has_negative_cycle() || !self.is_connected_to(t) {
+            return res;
+        }
+
+        let mut cur = t;
+        while cur != self.source {
+            res.push(cur);
+            cur = self.pre[cur] as usize;
+        }
+        res.push(self.source);
+
+        res.reverse();
+        res
+    }
+}
+
+// 从 start 开始沿着 pre 往回走，走到重复的顶点就找到了环。走到源点(pre 是自己)或者 -1 说明没有环
+pub(crate) fn find_cycle(pre: &[i32], start: usize) -> Option<Vec<usize>> {
+    // pos[v] 是 v 在这次行走中出现的位置
+    let mut pos = vec![None; pre.len()];
+    let mut walk = vec![];
+
+    let mut cur = start;
+    loop {
+        if let Some(i) = pos[cur] {
+            // pre 是往回指的，所以反转后才是边的方向
+            let mut cycle: Vec<usize> = walk[i..].to_vec();
+            cycle.reverse();
+            return Some(cycle);
+        }
+        pos[cur] = Some(walk.len());
+        walk.push(cur);
+
+        let p = pre[cur];
+        if p < 0 || p as usize == cur {
+            return None;
+        }
+        cur = p as usize;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bellman_ford_test() {
+        let graph = WeightedGraph::from_file("g_weighted.txt");
+        let bf = BellmanFord::new(&graph, 0).unwrap();
+
+        assert!(!bf.has_negative_cycle());
+        assert_eq!(bf.dis_to(), vec![0.0, 3.0, 2.0, 5.0, 6.0]);
+        assert_eq!(bf.path(3), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn negative_weight_test() {
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("5 6\n0 1 4\n0 2 2\n2 1 -3\n1 3 2\n3 4 -1\n2 4 5").unwrap();
+
+        let bf = BellmanFord::new(&graph, 0).unwrap();
+
+        assert!(!bf.has_negative_cycle());
+        assert_eq!(bf.dis(1), -1.0);
+        assert_eq!(bf.dis(4), 0.0);
+        assert_eq!(bf.path(4), vec![0, 2, 1, 3, 4]);
+    }
+
+    #[test]
+    fn negative_cycle_test() {
+        // 1 -> 2 -> 3 -> 1 的权值和是 -1
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("5 5\n0 1 1\n1 2 2\n2 3 -4\n3 1 1\n3 4 1").unwrap();
+
+        let bf = BellmanFord::new(&graph, 0).unwrap();
+
+        assert!(bf.has_negative_cycle());
+        let cycle = bf.negative_cycle().unwrap();
+        assert_eq!(cycle.len(), 3);
+
+        // 环上相邻顶点之间都要有边，并且权值和为负
+        let mut sum = 0.0;
+        for i in 0..cycle.len() {
+            let (v, w) = (cycle[i], cycle[(i + 1) % cycle.len()]);
+            assert!(graph.has_edge(v, w));
+            sum += graph.weight(v, w).unwrap();
+        }
+        assert!(sum < 0.0);
+        assert!(bf.path(4).is_empty());
+
+        // 从 4 出发到不了负权环
+        let bf = BellmanFord::new(&graph, 4).unwrap();
+        assert!(!bf.has_negative_cycle());
+
+        // 源点自己就在负权环上，pre[源点] 也会被改掉
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("3 3\n0 1 1\n1 2 -3\n2 0 1").unwrap();
+        let bf = BellmanFord::new(&graph, 0).unwrap();
+        assert!(bf.has_negative_cycle());
+        assert_eq!(bf.negative_cycle().unwrap().len(), 3);
+    }
+}
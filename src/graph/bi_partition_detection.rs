@@ -1,69 +1,91 @@
-use std::borrow::{Borrow, BorrowMut};
-use std::cell::{Ref, RefCell};
-use crate::graph::graph::Graph;
+use crate::graph::adj_iterable::AdjIterable;
 
+// 二分图检测，只借用图，构造的时候就完成染色
 #[derive(Debug, Clone)]
-struct BiPartitionDetection {
-    visited: RefCell<Vec<bool>>,
-    graph: RefCell<Graph>,
-    colors: RefCell<Vec<i32>>,
-    is_bipartite: bool,
+pub struct BiPartitionDetection<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    colors: Vec<i32>,
+    // pre[v] 是 dfs 中 v 的上一个顶点，用来找出奇数长度的环
+    pre: Vec<usize>,
+    odd_cycle: Option<Vec<usize>>,
 }
 
 
-impl BiPartitionDetection {
-    fn new() -> Self {
-        // let file_path = "g.txt";
-        let file_path = "g_not_bipartite.txt";
-        let mut graph = Graph::new();
-        Graph::init_matrix(&mut graph, file_path);
+impl<'a, G: AdjIterable> BiPartitionDetection<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        let v_size = graph.v();
 
-        let v = vec![false; graph.v];
-        let v_size = v.len();
+        let mut bp = Self {
+            graph,
+            visited: vec![false; v_size],
+            colors: vec![-1; v_size],
+            pre: (0..v_size).collect(),
+            odd_cycle: None,
+        };
 
-        Self {
-            visited: RefCell::new(v),
-            graph: RefCell::new(graph),
-            colors: RefCell::new(vec![-1; v_size]),
-            is_bipartite: true
-        }
-    }
-
-    fn process(&mut self) {
-        for v in 0..self.graph.borrow().v {
-            if !self.visited.borrow()[v] {
-                if !self.dfs(v, 0) {     // 起始染色成 0
-                    self.is_bipartite = false;
-                    break;
-                }
+        for v in 0..v_size {
+            // 起始染色成 0
+            if !bp.visited[v] && !bp.dfs(v, 0) {
+                break;
             }
         }
+        bp
     }
 
     // 是什么颜色不重要，重要的是能把颜色染成不一样
-    fn dfs(&self, v: usize, color: i32) -> bool {
-        self.visited.borrow_mut()[v] = true;
-        self.colors.borrow_mut()[v] = color;
+    fn dfs(&mut self, v: usize, color: i32) -> bool {
+        self.visited[v] = true;
+        self.colors[v] = color;
 
-        let g = self.graph.borrow();
+        let g = self.graph;
         for w in g.adj(v) {
             // 之前没有被访问，就要进行染色
-            if !self.visited.borrow_mut()[*w] {
-                // 给相邻顶点染色。取反操作，如果 v 的颜色是0，那么 w 就是1，反过来，如果 v 是 0，w 就是 1
+            if !self.visited[w] {
+                // 给相邻顶点染色。取反操作，如果 v 的颜色是0，那么 w 就是1，反过来，如果 v 是 1，w 就是 0
                 // 只要检测到不是二分图就不用继续检测了，直接返回false
-                if !self.dfs(*w, 1 - color) {
+                self.pre[w] = v;
+                if !self.dfs(w, 1 - color) {
                     return false;
                 }
-            } else if self.colors.borrow()[*w] == self.colors.borrow()[v] {
+            } else if self.colors[w] == self.colors[v] {
                 // 如果 w 顶点已经被访问过，那么应该是已经染上颜色了的。如果这两个相邻顶点的颜色相同，那么这张图就不是二分图
+                // 第一次发现冲突时 w 一定是 v 的祖先，沿着 pre 从 v 走回 w，加上边 v-w 就是一个奇数长度的环
+                let mut cycle = vec![v];
+                let mut cur = v;
+                while cur != w {
+                    cur = self.pre[cur];
+                    cycle.push(cur);
+                }
+                cycle.reverse();
+                self.odd_cycle = Some(cycle);
                 return false;
             }
         }
         true
     }
 
-    fn is_bipartite(&self) -> bool {
-        self.is_bipartite
+    pub fn is_bipartite(&self) -> bool {
+        self.odd_cycle.is_none()
+    }
+
+    // 每个顶点的颜色 0 或 1，没有访问到的顶点是 -1。不是二分图时染色是不完整的
+    pub fn colors(&self) -> &[i32] {
+        &self.colors
+    }
+
+    // 不是二分图时，返回一个奇数长度的环作为证据，环的最后一个顶点和第一个顶点相邻
+    pub fn odd_cycle(&self) -> Option<&[usize]> {
+        self.odd_cycle.as_deref()
+    }
+
+    // 二分图的两边，颜色为 0 的顶点是一边，颜色为 1 的顶点是另一边，不是二分图返回 None
+    pub fn sides(&self) -> Option<(Vec<usize>, Vec<usize>)> {
+        if !self.is_bipartite() {
+            return None;
+        }
+
+        Some((0..self.colors.len()).partition(|&v| self.colors[v] == 0))
     }
 }
 
@@ -71,13 +93,42 @@ impl BiPartitionDetection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn bp_test() {
-        let mut bp = BiPartitionDetection::new();
-
-        bp.process();
+        let graph = Graph::from_file("g_not_bipartite.txt");
+        let bp = BiPartitionDetection::new(&graph);
 
         println!("{:?}", bp.is_bipartite());
+        assert!(!bp.is_bipartite());
+        assert_eq!(bp.sides(), None);
+    }
+
+    #[test]
+    fn odd_cycle_test() {
+        let graph = Graph::from_file("g_not_bipartite.txt");
+        let bp = BiPartitionDetection::new(&graph);
+
+        let cycle = bp.odd_cycle().unwrap();
+        assert_eq!(cycle, vec![0, 1, 2]);
+        assert_eq!(cycle.len() % 2, 1);
+        for i in 0..cycle.len() {
+            assert!(graph.has_edge(cycle[i], cycle[(i + 1) % cycle.len()]));
+        }
+
+        let graph = Graph::from_file("g.txt");
+        let bp = BiPartitionDetection::new(&graph);
+        assert_eq!(bp.odd_cycle(), None);
+    }
+
+    #[test]
+    fn sides_test() {
+        let graph = Graph::from_file("g.txt");
+        let bp = BiPartitionDetection::new(&graph);
+
+        assert!(bp.is_bipartite());
+        assert_eq!(bp.colors(), vec![0, 1, 1, 0, 0, 0, 0]);
+        assert_eq!(bp.sides(), Some((vec![0, 3, 4, 5, 6], vec![1, 2])));
     }
-}
\ No newline at end of file
+}
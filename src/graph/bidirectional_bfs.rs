@@ -0,0 +1,203 @@
+use std::collections::VecDeque;
+use crate::graph::adj_iterable::AdjIterable;
+
+// 双向 bfs：从 s 往前、从 t 往后同时搜索，每次扩展队列较小的一边的一整层，两边相遇时就得到最短路径
+// 大图上单点对查询时，访问的顶点比从 s 出发的 bfs 少很多
+#[derive(Debug, Clone)]
+pub struct BidirectionalBFS<'a, G> {
+    graph: &'a G,
+    s: usize,
+    t: usize,
+    // 从 s 出发的距离和 pre，以及到 t 的距离和 next(从 t 一侧看的上一个顶点)
+    dis_s: Vec<i32>,
+    pre_s: Vec<i32>,
+    dis_t: Vec<i32>,
+    pre_t: Vec<i32>,
+    // 最短路径上两边相遇的顶点
+    meet: Option<usize>,
+    // 两边一共访问到的顶点数
+    visited_count: usize,
+}
+
+impl<'a, G: AdjIterable> BidirectionalBFS<'a, G> {
+    // 有向图从 t 一侧要沿着反向边搜索，每次查询都要先构建一个反图，是 O(V + E) 的时间和空间
+    // 同一个有向图上有很多次查询时，用 new_with_reverse 复用事先构建好的反图
+    pub fn new(graph: &'a G, s: usize, t: usize) -> Self {
+        if graph.is_directed() {
+            let reverse = graph.reverse();
+            return Self::new_with_reverse(graph, &reverse, s, t);
+        }
+        Self::new_with_reverse(graph, graph, s, t)
+    }
+
+    // reverse 是 graph 的反图，只在构造的时候用到。无向图的反图就是它自己，直接传入 graph
+    pub fn new_with_reverse(graph: &'a G, reverse: &G, s: usize, t: usize) -> Self {
+        let v_size = graph.v();
+        let _ = graph.validate_vertex(s);
+        let _ = graph.validate_vertex(t);
+
+        let mut bfs = Self {
+            graph,
+            s,
+            t,
+            dis_s: vec![-1; v_size],
+            pre_s: vec![-1; v_size],
+            dis_t: vec![-1; v_size],
+            pre_t: vec![-1; v_size],
+            meet: None,
+            visited_count: 0,
+        };
+
+        bfs.bfs(reverse);
+        bfs
+    }
+
+    fn bfs(&mut self, reverse: &G) {
+        let (s, t) = (self.s, self.t);
+        self.dis_s[s] = 0;
+        self.pre_s[s] = s as i32;
+        self.dis_t[t] = 0;
+        self.pre_t[t] = t as i32;
+        self.visited_count = if s == t { 1 } else { 2 };
+        if s == t {
+            self.meet = Some(s);
+            return;
+        }
+
+        let mut queue_s = VecDeque::from([s]);
+        let mut queue_t = VecDeque::from([t]);
+        // 当前找到的最短距离
+        let mut best = i32::MAX;
+
+        while !queue_s.is_empty() && !queue_t.is_empty() {
+            let forward = queue_s.len() <= queue_t.len();
+            let (graph, queue, dis, pre, other) = if forward {
+                (self.graph, &mut queue_s, &mut self.dis_s, &mut self.pre_s, &self.dis_t)
+            } else {
+                (reverse, &mut queue_t, &mut self.dis_t, &mut self.pre_t, &self.dis_s)
+            };
+
+            // 扩展一整层，这一层中所有的相遇点都要比较，才能保证得到的是最短的
+            for _ in 0..queue.len() {
+                let Some(v) = queue.pop_front() else { break };
+                for w in graph.adj(v) {
+                    if dis[w] == -1 {
+                        dis[w] = dis[v] + 1;
+                        pre[w] = v as i32;
+                        queue.push_back(w);
+                        self.visited_count += 1;
+                    }
+                    if other[w] != -1 && dis[w] + other[w] < best {
+                        best = dis[w] + other[w];
+                        self.meet = Some(w);
+                    }
+                }
+            }
+
+            if self.meet.is_some() {
+                break;
+            }
+        }
+    }
+
+    pub fn is_connected(&self) -> bool {
+        self.meet.is_some()
+    }
+
+    // s 到 t 的最短距离，不连通为 -1
+    pub fn dis(&self) -> i32 {
+        match self.meet {
+            Some(m) => self.dis_s[m] + self.dis_t[m],
+            None => -1,
+        }
+    }
+
+    // s 到 t 的最短路径，不连通返回空
+    pub fn path(&self) -> Vec<usize> {
+        let Some(meet) = self.meet else {
+            return vec![];
+        };
+
+        // 从相遇点沿着 pre_s 走回 s
+        let mut res = vec![];
+        let mut cur = meet;
+        while cur != self.s {
+            res.push(cur);
+            cur = self.pre_s[cur] as usize;
+        }
+        res.push(self.s);
+        res.reverse();
+
+        // 再从相遇点沿着 pre_t 走到 t
+        let mut cur = meet;
+        while cur != self.t {
+            cur = self.pre_t[cur] as usize;
+            res.push(cur);
+        }
+        res
+    }
+
+    // 两边一共访问到的顶点数，可以和普通的 bfs 比较
+    pub fn visited_count(&self) -> usize {
+        self.visited_count
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::csr_graph::CsrGraph;
+    use crate::graph::graph::Graph;
+    use crate::graph::test_util::grid_edges;
+    use crate::graph::u_s_s_s_path::USSSPath;
+
+    fn check<G: AdjIterable>(graph: &G) {
+        let reverse = graph.reverse();
+        for s in 0..graph.v() {
+            let expected = USSSPath::new(graph, s);
+            for t in 0..graph.v() {
+                let bfs = BidirectionalBFS::new_with_reverse(graph, &reverse, s, t);
+                assert_eq!(bfs.path(), BidirectionalBFS::new(graph, s, t).path());
+                assert_eq!(bfs.dis(), expected.dis(t));
+                assert_eq!(bfs.is_connected(), expected.is_connected_to(t));
+
+                // 路径上相邻的顶点都有边，长度就是最短距离
+                let path = bfs.path();
+                if bfs.is_connected() {
+                    assert_eq!(path.len() as i32, bfs.dis() + 1);
+                    assert_eq!((path[0], *path.last().unwrap()), (s, t));
+                    assert!(path.windows(2).all(|pair| graph.has_edge(pair[0], pair[1])));
+                } else {
+                    assert!(path.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bidirectional_test() {
+        check(&Graph::from_file("g.txt"));
+        check(&Graph::from_file("g_bfs.txt"));
+        check(&Graph::from_file("g_bridges.txt"));
+        check(&Graph::from_file_directed("g_directed.txt"));
+        check(&Graph::from_file_directed("g_scc.txt"));
+
+        let graph = Graph::from_file("g_bfs.txt");
+        assert_eq!(BidirectionalBFS::new(&graph, 0, 5).path(), vec![0, 2, 6, 5]);
+    }
+
+    #[test]
+    fn large_graph_test() {
+        // 300 x 300 的网格，从左上角到中间
+        let n = 300;
+        let graph = CsrGraph::from_edges(n * n, grid_edges(n)).unwrap();
+        let (s, t) = (0, n / 2 * n + n / 2);
+
+        let bfs = BidirectionalBFS::new(&graph, s, t);
+        let expected = USSSPath::new(&graph, s);
+        assert_eq!(bfs.dis(), expected.dis(t));
+        assert_eq!(bfs.dis(), n as i32);
+        assert!(bfs.visited_count() < expected.order().len());
+    }
+}
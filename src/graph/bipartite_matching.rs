@@ -0,0 +1,241 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::bi_partition_detection::BiPartitionDetection;
+use crate::graph::error::{MatrixError::{DirectedGraph, NotBipartite}, Result};
+use crate::queue::queue::Queue;
+
+// 二分图最大匹配，先用 BiPartitionDetection 把顶点分成左右两边，再从左边的顶点出发找增广路径
+// 增广路径：从左边一个没匹配的顶点出发，非匹配边和匹配边交替，终止于右边一个没匹配的顶点
+#[derive(Debug, Clone)]
+pub struct BipartiteMatching<'a, G> {
+    graph: &'a G,
+    // 颜色为 0 的顶点在左边
+    left: Vec<usize>,
+    // matching[v] 是和 v 匹配的顶点，没有匹配为 None
+    matching: Vec<Option<usize>>,
+    max_matching: usize,
+}
+
+impl<'a, G: AdjIterable> BipartiteMatching<'a, G> {
+    // 匈牙利算法：每个左边的顶点用 dfs 找一次增广路径
+    pub fn new(graph: &'a G) -> Result<Self> {
+        let mut bm = Self::init(graph)?;
+
+        for i in 0..bm.left.len() {
+            let mut visited = vec![false; graph.v()];
+            if bm.dfs(bm.left[i], &mut visited) {
+                bm.max_matching += 1;
+            }
+        }
+        Ok(bm)
+    }
+
+    // Hopcroft-Karp：每一轮用 bfs 从所有没匹配的左边顶点出发分层，再用 dfs 沿着分层一次找多条不相交的最短增广路径
+    pub fn new_hopcroft_karp(graph: &'a G) -> Result<Self> {
+        let mut bm = Self::init(graph)?;
+
+        loop {
+            let mut dist = vec![-1; graph.v()];
+            let Some(limit) = bm.hk_bfs(&mut dist) else {
+                break;
+            };
+
+            for i in 0..bm.left.len() {
+                let v = bm.left[i];
+                if bm.matching[v].is_none() && bm.hk_dfs(v, &mut dist, limit) {
+                    bm.max_matching += 1;
+                }
+            }
+        }
+        Ok(bm)
+    }
+
+    fn init(graph: &'a G) -> Result<Self> {
+        if graph.is_directed() {
+            return Err(DirectedGraph);
+        }
+
+        let bp = BiPartitionDetection::new(graph);
+        let Some((left, _)) = bp.sides() else {
+            return Err(NotBipartite(bp.odd_cycle().unwrap_or_default().to_vec()));
+        };
+
+        Ok(Self {
+            graph,
+            left,
+            matching: vec![None; graph.v()],
+            max_matching: 0,
+        })
+    }
+
+    // 从左边的顶点 v 出发找增广路径，找到了就沿路径把匹配边和非匹配边互换
+    fn dfs(&mut self, v: usize, visited: &mut [bool]) -> bool {
+        let g = self.graph;
+        for w in g.adj(v) {
+            if visited[w] {
+                continue;
+            }
+            visited[w] = true;
+
+            // w 没有匹配，或者和 w 匹配的左边顶点可以换一个匹配
+            let u = self.matching[w];
+            if u.is_none_or(|u| self.dfs(u, visited)) {
+                self.matching[v] = Some(w);
+                self.matching[w] = Some(v);
+                return true;
+            }
+        }
+        false
+    }
+
+    // 只给左边的顶点分层，返回最短增广路径的最后一个左边顶点所在的层，没有增广路径返回 None
+    // 第一次到达右边没有匹配的顶点时，这一层就是最短增广路径的长度，更深的层不再扩展
+    fn hk_bfs(&self, dist: &mut [i32]) -> Option<i32> {
+        let g = self.graph;
+        let matching = &self.matching;
+        // 每个左边的顶点最多入队一次
+        let mut queue = Queue::new(self.left.len());
+        for &v in &self.left {
+            if matching[v].is_none() {
+                dist[v] = 0;
+                queue.enqueue(v).expect("queue capacity too small");
+            }
+        }
+
+        let mut limit = None;
+        while let Some(v) = queue.dequeue() {
+            if limit.is_some_and(|limit| dist[v] > limit) {
+                break;
+            }
+            for w in g.adj(v) {
+                match matching[w] {
+                    // 到达右边没有匹配的顶点，找到了最短的增广路径
+                    None => limit = Some(dist[v]),
+                    Some(u) => {
+                        if limit.is_none() && dist[u] == -1 {
+                            dist[u] = dist[v] + 1;
+                            queue.enqueue(u).expect("queue capacity too small");
+                        }
+                    }
+                }
+            }
+        }
+        limit
+    }
+
+    // 沿着分层往下走，只在第 limit 层的左边顶点结束增广路径，保证找到的都是最短增广路径
+    fn hk_dfs(&mut self, v: usize, dist: &mut [i32], limit: i32) -> bool {
+        let g = self.graph;
+        for w in g.adj(v) {
+            let u = self.matching[w];
+            let ok = match u {
+                None => dist[v] == limit,
+                Some(u) => dist[u] == dist[v] + 1 && self.hk_dfs(u, dist, limit),
+            };
+            if ok {
+                self.matching[v] = Some(w);
+                self.matching[w] = Some(v);
+                return true;
+            }
+        }
+
+        // 从 v 出发找不到增广路径，这一轮不再访问它
+        dist[v] = -1;
+        false
+    }
+
+    pub fn max_matching(&self) -> usize {
+        self.max_matching
+    }
+
+    // 所有顶点都被匹配
+    pub fn is_perfect(&self) -> bool {
+        self.max_matching * 2 == self.graph.v()
+    }
+
+    // 顶点 v 匹配的顶点
+    pub fn mate(&self, v: usize) -> Option<usize> {
+        let _ = self.graph.validate_vertex(v);
+        self.matching[v]
+    }
+
+    // 匹配的顶点对 (左边的顶点，右边的顶点)，按左边的顶点排序
+    pub fn matching(&self) -> Vec<(usize, usize)> {
+        self.left.iter()
+            .filter_map(|&v| self.matching[v].map(|w| (v, w)))
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::error::MatrixError;
+    use crate::graph::graph::Graph;
+    use crate::graph::test_util::{lcg, random_graph};
+
+    fn check_matching(bm: &BipartiteMatching<Graph>, graph: &Graph) {
+        let mut used = vec![false; graph.v];
+        for (v, w) in bm.matching() {
+            assert!(graph.has_edge(v, w));
+            assert!(!used[v] && !used[w]);
+            used[v] = true;
+            used[w] = true;
+        }
+        assert_eq!(bm.matching().len(), bm.max_matching());
+    }
+
+    #[test]
+    fn hungarian_test() {
+        let graph = Graph::from_file("g_matching.txt");
+        let bm = BipartiteMatching::new(&graph).unwrap();
+
+        assert_eq!(bm.max_matching(), 3);
+        assert!(!bm.is_perfect());
+        assert_eq!(bm.mate(7), None);
+        check_matching(&bm, &graph);
+    }
+
+    #[test]
+    fn hopcroft_karp_test() {
+        let graph = Graph::from_file("g_matching.txt");
+        let bm = BipartiteMatching::new_hopcroft_karp(&graph).unwrap();
+
+        assert_eq!(bm.max_matching(), 3);
+        check_matching(&bm, &graph);
+
+        // g.txt 中 1 和 2 都在右边，最多匹配两对
+        let graph = Graph::from_file("g.txt");
+        let hk = BipartiteMatching::new_hopcroft_karp(&graph).unwrap().max_matching();
+        assert_eq!(hk, 2);
+        assert_eq!(BipartiteMatching::new(&graph).unwrap().max_matching(), hk);
+    }
+
+    #[test]
+    fn not_bipartite_test() {
+        let graph = Graph::from_file("g_not_bipartite.txt");
+        match BipartiteMatching::new(&graph) {
+            Err(MatrixError::NotBipartite(cycle)) => assert_eq!(cycle, vec![0, 1, 2]),
+            _ => panic!("expected NotBipartite"),
+        }
+
+        let graph = Graph::from_file_directed("g_matching.txt");
+        let res = BipartiteMatching::new_hopcroft_karp(&graph);
+        assert!(matches!(res, Err(MatrixError::DirectedGraph)));
+    }
+
+    #[test]
+    fn compare_test() {
+        // 随机生成左边 0..10、右边 10..20 的二分图，两种算法的最大匹配数要一样
+        let mut rand = lcg(11);
+        for _ in 0..30 {
+            let pairs = (0..10).flat_map(|v| (10..20).map(move |w| (v, w)));
+            let graph = random_graph(20, pairs, 5, &mut rand);
+
+            let hungarian = BipartiteMatching::new(&graph).unwrap().max_matching();
+            let bm = BipartiteMatching::new_hopcroft_karp(&graph).unwrap();
+            assert_eq!(bm.max_matching(), hungarian);
+            check_matching(&bm, &graph);
+        }
+    }
+}
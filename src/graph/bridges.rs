@@ -0,0 +1,160 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::DirectedGraph, Result};
+
+// 无向图的桥(割边)、割点和点双联通分量
+// 在 dfs 的过程中记录 ord[v] (访问顺序) 和 low[v] (v 不经过父边能到达的最小的 ord)
+#[derive(Debug, Clone)]
+pub struct Bridges<'a, G> {
+    graph: &'a G,
+    ord: Vec<i32>,
+    low: Vec<i32>,
+    cnt: i32,
+    bridges: Vec<(usize, usize)>,
+    is_cut_point: Vec<bool>,
+    // dfs 过程中经过的边，找到一个点双联通分量时弹出
+    edge_stack: Vec<(usize, usize)>,
+    components: Vec<Vec<(usize, usize)>>,
+}
+
+impl<'a, G: AdjIterable> Bridges<'a, G> {
+    pub fn new(graph: &'a G) -> Result<Self> {
+        if graph.is_directed() {
+            return Err(DirectedGraph);
+        }
+
+        let v_size = graph.v();
+        let mut b = Self {
+            graph,
+            ord: vec![-1; v_size],
+            low: vec![0; v_size],
+            cnt: 0,
+            bridges: vec![],
+            is_cut_point: vec![false; v_size],
+            edge_stack: vec![],
+            components: vec![],
+        };
+
+        for v in 0..v_size {
+            if b.ord[v] == -1 {
+                b.dfs(v, v);
+            }
+        }
+        Ok(b)
+    }
+
+    fn dfs(&mut self, v: usize, parent: usize) {
+        self.ord[v] = self.cnt;
+        self.low[v] = self.cnt;
+        self.cnt += 1;
+
+        // 根节点在 dfs 树中的孩子个数
+        let mut children = 0;
+
+        let g = self.graph;
+        for w in g.adj(v) {
+            if self.ord[w] == -1 {
+                children += 1;
+                self.edge_stack.push((v, w));
+                self.dfs(w, v);
+
+                let low_w = self.low[w];
+                let ord_v = self.ord[v];
+                self.low[v] = self.low[v].min(low_w);
+
+                // w 不经过 v-w 这条边回不到 v 及以前的顶点，v-w 是桥
+                if low_w > ord_v {
+                    self.bridges.push((v, w));
+                }
+
+                // w 回不到 v 以前的顶点，v 是割点(根节点另外判断)，栈中 v-w 以上的边组成一个点双联通分量
+                if low_w >= ord_v {
+                    if v != parent {
+                        self.is_cut_point[v] = true;
+                    }
+
+                    let mut component = vec![];
+                    while let Some(edge) = self.edge_stack.pop() {
+                        component.push(edge);
+                        if edge == (v, w) {
+                            break;
+                        }
+                    }
+                    self.components.push(component);
+                }
+            } else if w != parent {
+                let ord_w = self.ord[w];
+                // 只记录指向祖先的反向边，避免同一条边入栈两次
+                if ord_w < self.ord[v] {
+                    self.edge_stack.push((v, w));
+                }
+                self.low[v] = self.low[v].min(ord_w);
+            }
+        }
+
+        // 根节点有两个以上的孩子才是割点
+        if v == parent && children > 1 {
+            self.is_cut_point[v] = true;
+        }
+    }
+
+    // 所有的桥，(v, w) 中 v 是 dfs 树中的父节点
+    pub fn bridges(&self) -> &[(usize, usize)] {
+        &self.bridges
+    }
+
+    // 所有的割点，从小到大
+    pub fn cut_points(&self) -> Vec<usize> {
+        self.is_cut_point.iter()
+            .enumerate()
+            .filter(|(_, &is_cut)| is_cut)
+            .map(|(v, _)| v)
+            .collect()
+    }
+
+    // 点双联通分量，每个分量是它包含的边
+    pub fn biconnected_components(&self) -> &[Vec<(usize, usize)>] {
+        &self.components
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_matrix::AdjMatrix;
+    use crate::graph::graph::Graph;
+
+    #[test]
+    fn bridges_test() {
+        let graph = Graph::from_file("g.txt");
+        let b = Bridges::new(&graph).unwrap();
+        assert_eq!(b.bridges(), [(2, 6), (1, 4)]);
+        assert_eq!(b.cut_points(), vec![1, 2]);
+
+        let graph = AdjMatrix::from_file("g_bridges.txt");
+        let b = Bridges::new(&graph).unwrap();
+
+        let mut bridges: Vec<_> = b.bridges().iter().map(|&(v, w)| (v.min(w), v.max(w))).collect();
+        bridges.sort();
+        assert_eq!(bridges, vec![(3, 5), (4, 7), (6, 8)]);
+        assert_eq!(b.cut_points(), vec![3, 4, 5, 6, 8]);
+    }
+
+    #[test]
+    fn biconnected_test() {
+        let graph = Graph::from_file("g_bridges.txt");
+        let b = Bridges::new(&graph).unwrap();
+
+        let comps = b.biconnected_components();
+        let mut sizes: Vec<usize> = comps.iter().map(|c| c.len()).collect();
+        sizes.sort();
+        // 两个单边的桥，一个三角形，一个四边形，一个 K4，再加上桥 6-8
+        assert_eq!(sizes, vec![1, 1, 1, 3, 4, 6]);
+        assert_eq!(comps.iter().map(|c| c.len()).sum::<usize>(), 16);
+    }
+
+    #[test]
+    fn directed_test() {
+        assert!(Bridges::new(&Graph::from_file_directed("g.txt")).is_err());
+    }
+}
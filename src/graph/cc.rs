@@ -1,86 +1,116 @@
-use std::cell::{Ref, RefCell};
-use crate::graph::graph::Graph;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::stack::stack::Stack;
 
 // connected component
+// 只借用图，在构造的时候就计算好每个顶点属于哪个联通分量
 #[derive(Debug, Clone)]
-struct CC {
-    visited: RefCell<Vec<i32>>,
-    order: RefCell<Vec<i32>>,
-    graph: RefCell<Graph>,
-    cc_count: RefCell<i32>,
+pub struct CC<'a, G> {
+    graph: &'a G,
+    // visited[v] 是顶点 v 所在联通分量的编号，-1 表示还没有访问
+    visited: Vec<i32>,
+    order: Vec<i32>,
+    cc_count: i32,
 }
 
 
-impl CC {
-    fn new() -> Self {
-        let file_path = "g.txt";
-        let mut graph = Graph::new();
-        Graph::init_matrix(&mut graph, file_path);
-
-        // 把 visited 数组初始化为 -1
-        let v = vec![-1; graph.v];
-
-        Self {
-            visited: RefCell::new(v),
-            order: RefCell::new(vec![]),
-            graph: RefCell::new(graph),
-            cc_count: RefCell::new(0)
+impl<'a, G: AdjIterable> CC<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        let mut cc = Self::init(graph);
+        for v in 0..graph.v() {
+            if cc.visited[v] == -1 {
+                cc.dfs(v, cc.cc_count);
+                cc.cc_count += 1;
+            }
         }
+        cc
     }
 
-    fn process(&mut self) {
-        for v in 0..self.graph.borrow().v {
-            if self.visited.borrow_mut()[v] == -1 {
-                self.dfs(v as i32, self.cc_count.clone());
-                *self.cc_count.borrow_mut() += 1;
+    // 用显式的栈代替递归，结果和 new 一样，很大的图也不会栈溢出
+    pub fn new_iterative(graph: &'a G) -> Self {
+        let mut cc = Self::init(graph);
+        for v in 0..graph.v() {
+            if cc.visited[v] == -1 {
+                cc.dfs_iterative(v, cc.cc_count);
+                cc.cc_count += 1;
             }
         }
+        cc
     }
 
-    // 计算联通分量
-    fn count_cc(&self) -> i32 {
-        // 这里要注意不能直接 take，因为 take 了后，visited 数组会为空，导致 is_connected 方法访问空数组
-        for i in self.visited.clone().take() {
-            print!("{} ", i);
+    fn init(graph: &'a G) -> Self {
+        // 把 visited 数组初始化为 -1
+        Self {
+            graph,
+            visited: vec![-1; graph.v()],
+            order: vec![],
+            cc_count: 0,
         }
-        println!();
-        self.cc_count.clone().take()
     }
 
-    fn order(&self) -> Vec<i32> {
-        self.order.borrow().clone()
+    // 联通分量的个数
+    pub fn count_cc(&self) -> i32 {
+        self.cc_count
     }
 
-    // 深度优先遍历
-    fn dfs(&self, v: i32, ccid: RefCell<i32>) {
+    pub fn order(&self) -> &[i32] {
+        &self.order
+    }
+
+    // 每个顶点所在联通分量的编号
+    pub fn ids(&self) -> &[i32] {
+        &self.visited
+    }
 
-        self.visited.borrow_mut()[v as usize] = ccid.take();
-        self.order.borrow_mut().push(v);
+    // 深度优先遍历
+    fn dfs(&mut self, v: usize, ccid: i32) {
+        self.visited[v] = ccid;
+        self.order.push(v as i32);
+
+        let g = self.graph;
+        for w in g.adj(v) {
+            if self.visited[w] == -1 {
+                self.dfs(w, ccid);
+            }
+        }
+    }
 
-        let g = self.graph.borrow();
-        for w in g.adj(v as usize) {
-            if self.visited.borrow_mut()[*w] == -1 {
-                self.dfs(*w as i32, ccid.clone());
+    // 栈中存放还没有遍历的相邻顶点，相当于递归时每一层的状态
+    fn dfs_iterative(&mut self, s: usize, ccid: i32) {
+        let g = self.graph;
+        let mut stack = Stack::new();
+
+        self.visited[s] = ccid;
+        self.order.push(s as i32);
+        stack.push(g.adj(s));
+
+        while let Some(iter) = stack.peek_mut() {
+            match iter.next() {
+                Some(w) => {
+                    if self.visited[w] == -1 {
+                        self.visited[w] = ccid;
+                        self.order.push(w as i32);
+                        stack.push(g.adj(w));
+                    }
+                }
+                None => {
+                    stack.pop();
+                }
             }
         }
     }
 
     // 判断两个顶点是否在同一个联通分量中
-    fn is_connected(&self, v: usize, w: usize) -> bool {
-        let _ = self.graph.borrow().validate_vertex(v);
-        let _ = self.graph.borrow().validate_vertex(w);
-        self.visited.borrow()[v] == self.visited.borrow()[w]
+    pub fn is_connected(&self, v: usize, w: usize) -> bool {
+        let _ = self.graph.validate_vertex(v);
+        let _ = self.graph.validate_vertex(w);
+        self.visited[v] == self.visited[w]
     }
 
     // 查看整张图有多少联通分量，每个联通分量包含哪些顶点
-    fn components(&self) -> Vec<Vec<i32>> {
-        // let mut res: Vec<Vec<i32>> = (0..self.cc_count.clone().take()).map(|_| Vec::new()).collect();
-        let mut res = vec![Vec::new(); self.cc_count.clone().take() as usize];
-
-        let graph = self.graph.borrow();
-        let visited = self.visited.borrow();
-        for v in 0..graph.v {
-            res[visited[v] as usize].push(v as i32);
+    pub fn components(&self) -> Vec<Vec<i32>> {
+        let mut res = vec![Vec::new(); self.cc_count as usize];
+        for (v, &ccid) in self.visited.iter().enumerate() {
+            res[ccid as usize].push(v as i32);
         }
 
         res
@@ -90,30 +120,27 @@ impl CC {
 
 #[cfg(test)]
 mod tests {
+    use std::thread;
     use super::*;
+    use crate::graph::adj_list::AdjList;
+    use crate::graph::adj_matrix::AdjMatrix;
+    use crate::graph::adj_set::AdjSet;
+    use crate::graph::csr_graph::CsrGraph;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn dfs_test() {
-        let mut cc = CC::new();
-
-        cc.process();
+        let graph = Graph::from_file("g.txt");
+        let cc = CC::new(&graph);
 
         println!("{:?}", cc.order());
         println!("{:?}", cc.count_cc());
-        // println!("visited: {:?}", cc.visited);
 
         // visited: 0 0 0 0 0 1 0
+        assert_eq!(cc.ids(), &[0, 0, 0, 0, 0, 1, 0]);
         assert_eq!(cc.is_connected(0, 6), true);
         assert_eq!(cc.is_connected(0, 5), false);
 
-        // let comp = cc.components();
-        // for ccid in 0..comp.len() {
-        //     print!("{}: ", ccid);
-        //     for w in &comp[ccid] {
-        //         print!("{} ", *w);
-        //     }
-        //     println!();
-        // }
         for (ccid, comp) in cc.components().iter().enumerate() {
             let mut output = format!("{}: ", ccid);
             for w in comp {
@@ -122,4 +149,72 @@ mod tests {
             println!("{}", output);
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn backends_test() {
+        let graph = Graph::from_file("g.txt");
+        let expected = CC::new(&graph);
+
+        let matrix = AdjMatrix::from_file("g.txt");
+        let matrix = CC::new(&matrix);
+        let list = AdjList::from_file("g.txt");
+        let list = CC::new(&list);
+        let set = AdjSet::from_file("g.txt");
+        let set = CC::new(&set);
+
+        // 不管底层用哪种表示，联通分量都应该一样
+        assert_eq!(expected.count_cc(), 2);
+        assert_eq!(matrix.count_cc(), expected.count_cc());
+        assert_eq!(list.count_cc(), expected.count_cc());
+        assert_eq!(set.count_cc(), expected.count_cc());
+
+        assert_eq!(matrix.components(), expected.components());
+        assert_eq!(list.components(), expected.components());
+        assert_eq!(set.components(), expected.components());
+    }
+
+    #[test]
+    fn from_graph_test() {
+        let mut graph = Graph::new();
+        graph.read_data("6 3\n0 1\n2 3\n3 4").unwrap();
+
+        let cc = CC::new(&graph);
+        assert_eq!(cc.count_cc(), 3);
+        assert_eq!(cc.components(), vec![vec![0, 1], vec![2, 3, 4], vec![5]]);
+    }
+
+    #[test]
+    fn iterative_test() {
+        let graph = Graph::from_file("g.txt");
+        let expected = CC::new(&graph);
+        let cc = CC::new_iterative(&graph);
+
+        assert_eq!(cc.order(), expected.order());
+        assert_eq!(cc.components(), expected.components());
+
+        // 两条各五十万个顶点的路径
+        let n = 1_000_000;
+        let graph = CsrGraph::from_edges(n, (0..n - 1).filter(|&v| v != n / 2 - 1).map(|v| (v, v + 1))).unwrap();
+        let cc = CC::new_iterative(&graph);
+
+        let components = cc.components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].len(), n / 2);
+        assert!(cc.is_connected(0, n / 2 - 1));
+        assert!(!cc.is_connected(0, n / 2));
+    }
+
+    #[test]
+    fn concurrent_test() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<CC<'_, Graph>>();
+
+        // 多个线程共享同一张图，不需要复制
+        let graph = Graph::from_file("g.txt");
+        let counts: Vec<i32> = thread::scope(|s| {
+            let handles: Vec<_> = (0..4).map(|_| s.spawn(|| CC::new(&graph).count_cc())).collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert_eq!(counts, vec![2; 4]);
+    }
+}
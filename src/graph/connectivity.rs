@@ -0,0 +1,151 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::*, Result};
+use crate::graph::union_find::UnionFind;
+
+// 增量的联通性索引：边一条一条地加进来，随时查询两个顶点是否联通、有多少个联通分量
+// CC 在图变化之后要重新 dfs 一遍，这里每加一条边只需要一次并查集的合并
+#[derive(Debug, Clone)]
+pub struct Connectivity {
+    uf: UnionFind,
+    // 已经加入的边数
+    e: usize,
+}
+
+impl Connectivity {
+    // n 个顶点，还没有边
+    pub fn new(n: usize) -> Self {
+        Self {
+            uf: UnionFind::new(n),
+            e: 0,
+        }
+    }
+
+    // 用已有的图初始化，有向图按照弱联通处理
+    pub fn from_graph<G: AdjIterable>(graph: &G) -> Self {
+        let mut connectivity = Self::new(graph.v());
+        for v in 0..graph.v() {
+            for w in graph.adj(v) {
+                // 无向图每条边会遍历到两次，只算一次
+                if graph.is_directed() || v < w {
+                    connectivity.uf.union(v, w);
+                    connectivity.e += 1;
+                }
+            }
+        }
+        connectivity
+    }
+
+    pub fn v(&self) -> usize {
+        self.uf.len()
+    }
+
+    pub fn e(&self) -> usize {
+        self.e
+    }
+
+    // 添加一个孤立的顶点，返回它的编号
+    pub fn add_vertex(&mut self) -> usize {
+        self.uf.push()
+    }
+
+    // 添加边 v-w，返回这条边是否把两个联通分量连在了一起
+    pub fn add_edge(&mut self, v: usize, w: usize) -> Result<bool> {
+        self.validate_vertex(v)?;
+        self.validate_vertex(w)?;
+
+        self.e += 1;
+        Ok(self.uf.union(v, w))
+    }
+
+    // 依次添加一批边
+    pub fn add_edges<I: IntoIterator<Item = (usize, usize)>>(&mut self, edges: I) -> Result<()> {
+        for (v, w) in edges {
+            self.add_edge(v, w)?;
+        }
+        Ok(())
+    }
+
+    // 查找时会做路径压缩，所以需要 &mut self
+    pub fn connected(&mut self, v: usize, w: usize) -> bool {
+        if self.validate_vertex(v).is_err() || self.validate_vertex(w).is_err() {
+            return false;
+        }
+        self.uf.is_connected(v, w)
+    }
+
+    // 联通分量的个数
+    pub fn count(&self) -> usize {
+        self.uf.count()
+    }
+
+    // v 所在联通分量的顶点个数
+    pub fn component_size(&mut self, v: usize) -> Result<usize> {
+        self.validate_vertex(v)?;
+        Ok(self.uf.size_of(v))
+    }
+
+    fn validate_vertex(&self, v: usize) -> Result<()> {
+        if v >= self.v() {
+            return Err(InvalidVertexEdge(v, self.v()));
+        }
+        Ok(())
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::cc::CC;
+    use crate::graph::graph::Graph;
+    use crate::graph::graph_builder::GraphBuilder;
+    use crate::graph::test_util::lcg;
+
+    #[test]
+    fn basic_test() {
+        let graph = Graph::from_file("g.txt");
+        let mut connectivity = Connectivity::from_graph(&graph);
+
+        assert_eq!(connectivity.e(), 6);
+        assert_eq!(connectivity.count(), 2);
+        assert!(connectivity.connected(0, 6));
+        assert!(!connectivity.connected(0, 5));
+
+        assert!(connectivity.add_edge(5, 6).unwrap());
+        assert!(!connectivity.add_edge(3, 4).unwrap());
+        assert_eq!(connectivity.count(), 1);
+        assert_eq!(connectivity.component_size(5).unwrap(), 7);
+        assert!(matches!(connectivity.component_size(100), Err(InvalidVertexEdge(100, _))));
+
+        let v = connectivity.add_vertex();
+        assert_eq!(connectivity.count(), 2);
+        assert!(!connectivity.connected(0, v));
+        assert!(matches!(connectivity.add_edge(0, 9), Err(InvalidVertexEdge(9, 8))));
+    }
+
+    #[test]
+    fn cross_check_test() {
+        // 用线性同余生成一串伪随机的边，每加一条边都和重新计算的 CC 对比
+        let n = 50;
+        let mut rand = lcg(7);
+        let mut next = || rand() as usize % n;
+
+        let mut graph = Graph::with_vertices(n);
+        let mut connectivity = Connectivity::new(n);
+        for _ in 0..80 {
+            let (v, w) = (next(), next());
+            // 自环边和平行边 Graph 不接受，跳过
+            if graph.add_edge(v, w).is_err() {
+                continue;
+            }
+            connectivity.add_edge(v, w).unwrap();
+
+            let cc = CC::new(&graph);
+            assert_eq!(connectivity.count(), cc.count_cc() as usize);
+            for (a, b) in [(v, w), (0, v), (w, n - 1), (next(), next())] {
+                assert_eq!(connectivity.connected(a, b), cc.is_connected(a, b));
+            }
+        }
+        assert_eq!(connectivity.e(), graph.e());
+    }
+}
@@ -0,0 +1,261 @@
+use std::iter::Copied;
+use std::slice;
+use crate::graph::adj_iterable::{AdjIterable, Edges};
+use crate::graph::error::{MatrixError::*, Result};
+
+// 压缩稀疏行(CSR)表示的只读图：顶点 v 的相邻顶点是 targets[offsets[v]..offsets[v + 1]]，并且已经排好序
+// 只用两个数组存储，比 Vec<BTreeSet<usize>> 省内存，遍历时也是连续访问，适合很大的图
+#[derive(Debug, Clone)]
+pub struct CsrGraph {
+    v: usize,
+    e: usize,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    directed: bool,
+}
+
+impl CsrGraph {
+    // 从任意一种底层表示构建
+    pub fn from_graph<G: AdjIterable>(graph: &G) -> Self {
+        let mut offsets = Vec::with_capacity(graph.v() + 1);
+        let mut targets = vec![];
+        offsets.push(0);
+        for v in 0..graph.v() {
+            let start = targets.len();
+            targets.extend(graph.adj(v));
+            targets[start..].sort_unstable();
+            offsets.push(targets.len());
+        }
+
+        Self {
+            v: graph.v(),
+            e: graph.e(),
+            offsets,
+            targets,
+            directed: graph.is_directed(),
+        }
+    }
+
+    // 由边的列表构建无向图，和读文件一样，不允许自环边和平行边
+    // 边只存成 (v, w) 的列表，每条边 16 字节，不带权值
+    pub fn from_edges<I: IntoIterator<Item = (usize, usize)>>(n: usize, edges: I) -> Result<Self> {
+        let edges: Vec<(usize, usize)> = edges.into_iter().collect();
+        Self::build(n, edges.iter().copied(), false)
+    }
+
+    // 由边的列表构建有向图
+    pub fn from_edges_directed<I: IntoIterator<Item = (usize, usize)>>(n: usize, edges: I) -> Result<Self> {
+        let edges: Vec<(usize, usize)> = edges.into_iter().collect();
+        Self::build(n, edges.iter().copied(), true)
+    }
+
+    // 计数排序：第一遍统计每个顶点的度得到 offsets，第二遍把每条边填到对应的位置
+    // 边要遍历两遍，所以迭代器需要能 clone
+    fn build<I: Iterator<Item = (usize, usize)> + Clone>(n: usize, edges: I, directed: bool) -> Result<Self> {
+        let mut offsets = vec![0; n + 1];
+        let mut e = 0;
+        for (v, w) in edges.clone() {
+            if v >= n || w >= n {
+                return Err(InvalidVertexEdge(v.max(w), n));
+            }
+            if v == w {
+                return Err(SelfLoop(0));
+            }
+            offsets[v + 1] += 1;
+            if !directed {
+                offsets[w + 1] += 1;
+            }
+            e += 1;
+        }
+        for v in 0..n {
+            offsets[v + 1] += offsets[v];
+        }
+
+        let mut next = offsets.clone();
+        let mut targets = vec![0; offsets[n]];
+        for (v, w) in edges {
+            targets[next[v]] = w;
+            next[v] += 1;
+            if !directed {
+                targets[next[w]] = v;
+                next[w] += 1;
+            }
+        }
+
+        // 每个顶点的相邻顶点排好序，相邻两个相同就是平行边
+        for v in 0..n {
+            let row = &mut targets[offsets[v]..offsets[v + 1]];
+            row.sort_unstable();
+            if row.windows(2).any(|pair| pair[0] == pair[1]) {
+                return Err(ParallelEdge(0));
+            }
+        }
+
+        Ok(Self {
+            v: n,
+            e,
+            offsets,
+            targets,
+            directed,
+        })
+    }
+
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    pub fn targets(&self) -> &[usize] {
+        &self.targets
+    }
+
+    // 顶点 v 的相邻顶点
+    pub fn neighbors(&self, v: usize) -> &[usize] {
+        &self.targets[self.offsets[v]..self.offsets[v + 1]]
+    }
+}
+
+impl AdjIterable for CsrGraph {
+    type Iter<'a> = Copied<slice::Iter<'a, usize>>;
+
+    fn v(&self) -> usize {
+        self.v
+    }
+
+    fn e(&self) -> usize {
+        self.e
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn adj(&self, v: usize) -> Self::Iter<'_> {
+        let _ = self.validate_vertex(v);
+
+        self.neighbors(v).iter().copied()
+    }
+
+    // 相邻顶点是有序的，可以二分查找
+    fn has_edge(&self, v: usize, w: usize) -> bool {
+        let _ = self.validate_vertex(v);
+        let _ = self.validate_vertex(w);
+
+        self.neighbors(v).binary_search(&w).is_ok()
+    }
+
+    // 边已经在读取时校验过，不会有越界、自环边和平行边
+    fn from_edge_list(v: usize, _e: usize, edges: Edges, directed: bool) -> Self {
+        CsrGraph::build(v, edges.iter().map(|&(v, w, _)| (v, w)), directed).expect("edges are validated by the parser")
+    }
+
+    fn reverse(&self) -> Self {
+        if !self.directed {
+            return self.clone();
+        }
+
+        // 直接由原图的 offsets 和 targets 填充：先统计每个顶点的入度，再按 v 从小到大把 v 填到 w 的一行
+        // v 是递增的，所以每一行自然是有序的；原图没有自环边和平行边，反图也不会有
+        let mut offsets = vec![0; self.v + 1];
+        for &w in &self.targets {
+            offsets[w + 1] += 1;
+        }
+        for v in 0..self.v {
+            offsets[v + 1] += offsets[v];
+        }
+
+        let mut next = offsets.clone();
+        let mut targets = vec![0; self.targets.len()];
+        for v in 0..self.v {
+            for &w in self.neighbors(v) {
+                targets[next[w]] = v;
+                next[w] += 1;
+            }
+        }
+
+        Self {
+            v: self.v,
+            e: self.e,
+            offsets,
+            targets,
+            directed: true,
+        }
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.offsets[v + 1] - self.offsets[v]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::cc::CC;
+    use crate::graph::error::MatrixError;
+    use crate::graph::graph::Graph;
+    use crate::graph::graph_bfs::GraphBFS;
+    use crate::graph::graph_dfs::GraphDFS;
+    use crate::graph::scc::SCC;
+    use crate::graph::test_util::grid_edges;
+
+    #[test]
+    fn basic_test() {
+        let graph = Graph::from_file("g.txt");
+        let csr = CsrGraph::from_graph(&graph);
+
+        assert_eq!(csr.v(), 7);
+        assert_eq!(csr.e(), 6);
+        assert_eq!(csr.offsets(), &[0, 2, 5, 8, 10, 11, 11, 12]);
+        for v in 0..graph.v() {
+            assert_eq!(csr.adj(v).collect::<Vec<_>>(), graph.adj(v).collect::<Vec<_>>());
+            assert_eq!(csr.degree(v), graph.degree(v));
+        }
+        assert!(csr.has_edge(6, 2));
+        assert!(!csr.has_edge(6, 3));
+
+        // 从文件和边的列表构建得到的结果一样
+        let from_file = CsrGraph::from_file("g.txt");
+        let from_edges = CsrGraph::from_edges(7, [(0, 1), (0, 2), (1, 3), (1, 4), (2, 3), (2, 6)]).unwrap();
+        assert_eq!(from_file.targets(), csr.targets());
+        assert_eq!(from_edges.targets(), csr.targets());
+
+        assert!(matches!(CsrGraph::from_edges(3, [(0, 1), (1, 0)]), Err(MatrixError::ParallelEdge(0))));
+        assert!(matches!(CsrGraph::from_edges(3, [(1, 1)]), Err(MatrixError::SelfLoop(0))));
+        assert!(matches!(CsrGraph::from_edges(3, [(0, 3)]), Err(MatrixError::InvalidVertexEdge(3, 3))));
+    }
+
+    #[test]
+    fn algorithm_test() {
+        let csr = CsrGraph::from_file("g.txt");
+
+        let cc = CC::new(&csr);
+        assert_eq!(cc.count_cc(), 2);
+
+        let dfs = GraphDFS::new(&csr);
+        let graph = Graph::from_file("g.txt");
+        let expected = GraphDFS::new(&graph);
+        assert_eq!(dfs.order(), expected.order());
+
+        let csr = CsrGraph::from_file_directed("g_scc.txt");
+        assert_eq!(csr.reverse().reverse().targets(), csr.targets());
+        let expected = CsrGraph::from_graph(&Graph::from_file_directed("g_scc.txt").reverse());
+        assert_eq!(csr.reverse().offsets(), expected.offsets());
+        assert_eq!(csr.reverse().targets(), expected.targets());
+        let scc = SCC::new(&csr).unwrap();
+        assert_eq!(scc.count_scc(), 3);
+    }
+
+    #[test]
+    fn large_graph_test() {
+        // 1000 x 1000 的网格，一百万个顶点，大约两百万条边
+        let n = 1000;
+        let csr = CsrGraph::from_edges(n * n, grid_edges(n)).unwrap();
+        assert_eq!(csr.e(), 2 * n * (n - 1));
+        assert_eq!(csr.targets().len(), 2 * csr.e());
+
+        let bfs = GraphBFS::new(&csr);
+        let order = bfs.order();
+        assert_eq!(order.len(), n * n);
+        assert_eq!(order[..3], [0, 1, n]);
+    }
+}
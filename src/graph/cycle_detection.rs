@@ -1,66 +1,125 @@
-use std::cell::{Ref, RefCell};
-use crate::graph::graph::Graph;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::stack::stack::Stack;
 
+// 无向图的环检测，只借用图，构造的时候就完成检测
 #[derive(Debug, Clone)]
-struct CycleDetection {
-    visited: RefCell<Vec<bool>>,
-    order: RefCell<Vec<usize>>,
-    graph: RefCell<Graph>,
-    has_cycle: RefCell<bool>,
+pub struct CycleDetection<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    order: Vec<usize>,
+    // pre[v] 是 dfs 中 v 的上一个顶点
+    pre: Vec<usize>,
+    cycle: Option<Vec<usize>>,
 }
 
-impl CycleDetection {
-    fn new(file_path: &str) -> Self {
-        let mut graph = Graph::new();
-
-        Graph::init_matrix(&mut graph, file_path);
-
-        let v = vec![false; graph.v];
-
-        Self {
-            visited: RefCell::new(v),
-            order: RefCell::new(vec![]),
-            graph: RefCell::new(graph),
-            has_cycle: RefCell::new(false),
+impl<'a, G: AdjIterable> CycleDetection<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        let mut cd = Self::init(graph);
+        for v in 0..graph.v() {
+            // 初始化时，parent 是自己，找到一个环就够了
+            if !cd.visited[v] && cd.dfs(v, v) {
+                break;
+            }
         }
+        cd
     }
 
-    fn process(&mut self) {
-        for v in 0..self.graph.borrow().v {
-            if !self.visited.borrow()[v] {
-                if self.dfs(v, v) {                     // 初始化时，parent 是自己
-                    *self.has_cycle.borrow_mut() = true;
-                    break;                                    // 找到一个环就够了
-                }
+    // 用显式的栈代替递归，找到的环和 new 一样
+    pub fn new_iterative(graph: &'a G) -> Self {
+        let mut cd = Self::init(graph);
+        for v in 0..graph.v() {
+            if !cd.visited[v] && cd.dfs_iterative(v) {
+                break;
             }
         }
+        cd
+    }
+
+    fn init(graph: &'a G) -> Self {
+        Self {
+            graph,
+            visited: vec![false; graph.v()],
+            order: vec![],
+            pre: (0..graph.v()).collect(),
+            cycle: None,
+        }
     }
 
-    fn order(&self) -> Vec<usize> {
-        self.order.borrow().clone()
+    pub fn order(&self) -> &[usize] {
+        &self.order
     }
 
     // 从顶点 v 开始，判断图中是否有环
-    fn dfs(&self, v: usize, parent: usize) -> bool {
-        self.visited.borrow_mut()[v] = true;
-        self.order.borrow_mut().push(v);
+    fn dfs(&mut self, v: usize, parent: usize) -> bool {
+        self.visited[v] = true;
+        self.order.push(v);
 
-        let g = self.graph.borrow();
+        let g = self.graph;
         for w in g.adj(v) {
-            if !self.visited.borrow_mut()[*w] {
-                if self.dfs(*w, v) {
+            if !self.visited[w] {
+                self.pre[w] = v;
+                if self.dfs(w, v) {
                     return true;
                 }
-            } else if *w != parent {
-                // *self.has_cycle.borrow_mut() = true; 优化过后，这句移到上面执行
+            } else if w != parent {
+                // 第一次遇到的非父亲的已访问顶点 w 一定是 v 的祖先，沿着 pre 从 v 走回 w 就是环
+                self.cycle = Some(self.collect_cycle(v, w));
                 return true;
             }
         }
         false
     }
 
-    fn has_cycle(&self) -> bool {
-        self.has_cycle.clone().take()
+    // 栈中存放 (顶点，还没有遍历的相邻顶点)，v 的 parent 就是 pre[v]
+    fn dfs_iterative(&mut self, s: usize) -> bool {
+        self.visited[s] = true;
+        self.order.push(s);
+
+        let g = self.graph;
+        let mut stack = Stack::new();
+        stack.push((s, g.adj(s)));
+
+        while let Some((v, iter)) = stack.peek_mut() {
+            let v = *v;
+            match iter.next() {
+                Some(w) => {
+                    if !self.visited[w] {
+                        self.visited[w] = true;
+                        self.order.push(w);
+                        self.pre[w] = v;
+                        stack.push((w, g.adj(w)));
+                    } else if w != self.pre[v] {
+                        self.cycle = Some(self.collect_cycle(v, w));
+                        return true;
+                    }
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+        false
+    }
+
+    // 沿着 pre 从 v 走回祖先 w
+    fn collect_cycle(&self, v: usize, w: usize) -> Vec<usize> {
+        let mut cycle = vec![v];
+        let mut cur = v;
+        while cur != w {
+            cur = self.pre[cur];
+            cycle.push(cur);
+        }
+        cycle.reverse();
+        cycle
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    // 找到的环，环的最后一个顶点和第一个顶点相邻，没有环返回 None
+    pub fn cycle(&self) -> Option<&[usize]> {
+        self.cycle.as_deref()
     }
 }
 
@@ -68,15 +127,57 @@ impl CycleDetection {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::csr_graph::CsrGraph;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn dfs_test() {
-        let mut cd = CycleDetection::new("g.txt");
-        cd.process();
+        let graph = Graph::from_file("g.txt");
+        let cd = CycleDetection::new(&graph);
         println!("{:?}", cd.has_cycle());
 
-        let mut cd2 = CycleDetection::new("g_no_cycle.txt");
-        cd2.process();
+        let graph = Graph::from_file("g_no_cycle.txt");
+        let cd2 = CycleDetection::new(&graph);
         println!("{:?}", cd2.has_cycle());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn cycle_test() {
+        let graph = Graph::from_file("g.txt");
+        let cd = CycleDetection::new(&graph);
+
+        let cycle = cd.cycle().unwrap();
+        assert_eq!(cycle, vec![0, 1, 3, 2]);
+        for i in 0..cycle.len() {
+            assert!(graph.has_edge(cycle[i], cycle[(i + 1) % cycle.len()]));
+        }
+
+        let graph = Graph::from_file("g_no_cycle.txt");
+        let cd = CycleDetection::new(&graph);
+        assert!(!cd.has_cycle());
+        assert_eq!(cd.cycle(), None);
+    }
+
+    #[test]
+    fn iterative_test() {
+        for file_path in ["g.txt", "g_no_cycle.txt", "g_bridges.txt"] {
+            let graph = Graph::from_file(file_path);
+            let expected = CycleDetection::new(&graph);
+            let cd = CycleDetection::new_iterative(&graph);
+
+            assert_eq!(cd.has_cycle(), expected.has_cycle());
+            assert_eq!(cd.cycle(), expected.cycle());
+            assert_eq!(cd.order(), expected.order());
+        }
+
+        // 一百万个顶点的路径没有环，首尾相连之后整个路径就是一个环
+        let n = 1_000_000;
+        let graph = CsrGraph::from_edges(n, (0..n - 1).map(|v| (v, v + 1))).unwrap();
+        let cd = CycleDetection::new_iterative(&graph);
+        assert!(!cd.has_cycle());
+
+        let graph = CsrGraph::from_edges(n, (0..n).map(|v| (v, (v + 1) % n))).unwrap();
+        let cd = CycleDetection::new_iterative(&graph);
+        assert_eq!(cd.cycle().unwrap().len(), n);
+    }
+}
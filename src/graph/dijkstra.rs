@@ -0,0 +1,132 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::NegativeWeight, Result};
+use crate::graph::index_min_pq::IndexMinPQ;
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 带权图的单源最短路径，要求所有边的权值非负。只借用图，构造的时候就完成计算
+#[derive(Debug, Clone)]
+pub struct Dijkstra<'a> {
+    graph: &'a WeightedGraph,
+    visited: Vec<bool>,
+    pre: Vec<i32>,
+    source: usize,
+    dis: Vec<f64>,
+}
+
+impl<'a> Dijkstra<'a> {
+    // 图中有负权边时返回 NegativeWeight
+    pub fn new(graph: &'a WeightedGraph, source: usize) -> Result<Self> {
+        graph.validate_vertex(source)?;
+
+        for v in 0..graph.v() {
+            for (w, weight) in graph.adj_weighted(v) {
+                if weight < 0.0 {
+                    return Err(NegativeWeight(v, w, weight));
+                }
+            }
+        }
+
+        let v_size = graph.v();
+        let mut dijkstra = Self {
+            graph,
+            visited: vec![false; v_size],
+            pre: vec![-1; v_size],
+            source,
+            dis: vec![f64::INFINITY; v_size],
+        };
+        dijkstra.process();
+        Ok(dijkstra)
+    }
+
+    fn process(&mut self) {
+        let g = self.graph;
+
+        let mut pq = IndexMinPQ::new(g.v());
+        self.dis[self.source] = 0.0;
+        self.pre[self.source] = self.source as i32;
+        pq.push(self.source, 0.0);
+
+        // 每次从堆中取出当前距离最小的顶点，它的最短路径就确定了
+        while let Some((v, _)) = pq.pop() {
+            self.visited[v] = true;
+
+            // 用 v 去更新相邻顶点的距离
+            for (w, weight) in g.adj_weighted(v) {
+                if !self.visited[w] && self.dis[v] + weight < self.dis[w] {
+                    self.dis[w] = self.dis[v] + weight;
+                    self.pre[w] = v as i32;
+                    pq.push(w, self.dis[w]);
+                }
+            }
+        }
+    }
+
+    pub fn is_connected_to(&self, t: usize) -> bool {
+        let _ = self.graph.validate_vertex(t);
+        self.visited[t]
+    }
+
+    // 从源点到 t 的最短距离，不可达时是无穷大
+    pub fn dis(&self, t: usize) -> f64 {
+        let _ = self.graph.validate_vertex(t);
+        self.dis[t]
+    }
+
+    pub fn path(&self, target: usize) -> Vec<usize> {
+        let mut res = vec![];
+        if !self.is_connected_to(target) {
+            return res;
+        }
+
+        let mut cur = target;
+        while cur != self.source {
+            res.push(cur);
+            cur = self.pre[cur] as usize;
+        }
+        res.push(self.source);
+
+        res.reverse();
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::error::MatrixError;
+
+    #[test]
+    fn dijkstra_test() {
+        let graph = WeightedGraph::from_file("g_weighted.txt");
+        let dijkstra = Dijkstra::new(&graph, 0).unwrap();
+
+        let dis: Vec<f64> = (0..5).map(|t| dijkstra.dis(t)).collect();
+        assert_eq!(dis, vec![0.0, 3.0, 2.0, 5.0, 6.0]);
+        assert_eq!(dijkstra.path(3), vec![0, 2, 1, 3]);
+        assert_eq!(dijkstra.path(0), vec![0]);
+    }
+
+    #[test]
+    fn unreachable_test() {
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("4 3\n0 1 1.5\n1 2 2\n3 0 1").unwrap();
+
+        let dijkstra = Dijkstra::new(&graph, 0).unwrap();
+
+        assert_eq!(dijkstra.dis(2), 3.5);
+        assert!(!dijkstra.is_connected_to(3));
+        assert_eq!(dijkstra.dis(3), f64::INFINITY);
+        assert!(dijkstra.path(3).is_empty());
+    }
+
+    #[test]
+    fn negative_weight_test() {
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("3 2\n0 1 2\n1 2 -1").unwrap();
+
+        match Dijkstra::new(&graph, 0) {
+            Err(MatrixError::NegativeWeight(v, w, weight)) => assert_eq!((v, w, weight), (1, 2, -1.0)),
+            _ => panic!("negative weight should be rejected"),
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::UndirectedGraph, Result};
+
+// 有向图的环检测，不能像无向图那样用 parent 判断
+// 三色标记：0 未访问，1 在当前的递归路径上，2 已经访问完。遇到颜色为 1 的顶点就说明有环
+#[derive(Debug, Clone)]
+pub struct DirectedCycleDetection<'a, G> {
+    graph: &'a G,
+    colors: Vec<u8>,
+    pre: Vec<i32>,
+    // 后序遍历的顺序，反过来就是拓扑序
+    post_order: Vec<usize>,
+    cycle: Option<Vec<usize>>,
+}
+
+impl<'a, G: AdjIterable> DirectedCycleDetection<'a, G> {
+    pub fn new(graph: &'a G) -> Result<Self> {
+        if !graph.is_directed() {
+            return Err(UndirectedGraph);
+        }
+
+        let v_size = graph.v();
+        let mut cd = Self {
+            graph,
+            colors: vec![0; v_size],
+            pre: vec![-1; v_size],
+            post_order: vec![],
+            cycle: None,
+        };
+
+        for v in 0..v_size {
+            if cd.colors[v] == 0 && cd.dfs(v) {
+                break;                      // 找到一个环就够了
+            }
+        }
+        Ok(cd)
+    }
+
+    fn dfs(&mut self, v: usize) -> bool {
+        self.colors[v] = 1;
+
+        let g = self.graph;
+        for w in g.adj(v) {
+            let color = self.colors[w];
+            if color == 0 {
+                self.pre[w] = v as i32;
+                if self.dfs(w) {
+                    return true;
+                }
+            } else if color == 1 {
+                // w 还在递归路径上，沿着 pre 从 v 走回 w 就是环
+                let mut cycle = vec![v];
+                let mut cur = v;
+                while cur != w {
+                    cur = self.pre[cur] as usize;
+                    cycle.push(cur);
+                }
+                cycle.reverse();
+                self.cycle = Some(cycle);
+                return true;
+            }
+        }
+
+        self.colors[v] = 2;
+        self.post_order.push(v);
+        false
+    }
+
+    pub fn has_cycle(&self) -> bool {
+        self.cycle.is_some()
+    }
+
+    // 找到的环，例如 [1, 2, 3] 表示 1 -> 2 -> 3 -> 1
+    pub fn cycle(&self) -> Option<&[usize]> {
+        self.cycle.as_deref()
+    }
+
+    pub fn post_order(&self) -> &[usize] {
+        &self.post_order
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::graph::Graph;
+
+    #[test]
+    fn dag_test() {
+        let graph = Graph::from_file_directed("g_dag.txt");
+        let cd = DirectedCycleDetection::new(&graph).unwrap();
+        assert!(!cd.has_cycle());
+        assert_eq!(cd.post_order().len(), 6);
+    }
+
+    #[test]
+    fn cycle_test() {
+        let mut graph = Graph::new_directed();
+        graph.read_data("5 6\n0 1\n1 2\n2 3\n3 1\n3 4\n4 0").unwrap();
+
+        let cd = DirectedCycleDetection::new(&graph).unwrap();
+
+        let cycle = cd.cycle().unwrap();
+        assert_eq!(cycle, [1, 2, 3]);
+        for i in 0..cycle.len() {
+            assert!(graph.has_edge(cycle[i], cycle[(i + 1) % cycle.len()]));
+        }
+
+        // 无向图不能用
+        assert!(DirectedCycleDetection::new(&Graph::from_file("g.txt")).is_err());
+    }
+}
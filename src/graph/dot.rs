@@ -0,0 +1,394 @@
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::str::FromStr;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::*, Result};
+use crate::graph::graph_builder::GraphBuilder;
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 给顶点染色用的颜色，分组编号超过颜色个数时循环使用
+const PALETTE: [&str; 8] = ["lightblue", "lightgreen", "lightpink", "lightyellow", "lightgray", "orange", "cyan", "violet"];
+
+// 导出 Graphviz DOT 格式时的选项
+#[derive(Debug, Clone)]
+pub struct DotOptions {
+    pub name: String,
+    // 要高亮的路径，例如 Path::path 的结果，路径上的顶点和边标成红色
+    pub path: Vec<usize>,
+    // groups[v] 是顶点 v 的分组，相同分组的顶点颜色相同，小于 0 的不染色
+    pub groups: Vec<i32>,
+}
+
+impl Default for DotOptions {
+    fn default() -> Self {
+        Self {
+            name: "G".to_owned(),
+            path: vec![],
+            groups: vec![],
+        }
+    }
+}
+
+impl DotOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn highlight_path(mut self, path: Vec<usize>) -> Self {
+        self.path = path;
+        self
+    }
+
+    // 按分组染色，例如 BiPartitionDetection::colors 的结果
+    pub fn color_groups(mut self, groups: Vec<i32>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    // 按联通分量染色，参数是 CC::components 的结果
+    pub fn color_components(mut self, components: &[Vec<i32>]) -> Self {
+        let v_size = components.iter().flatten().map(|&v| v as usize + 1).max().unwrap_or(0);
+        self.groups = vec![-1; v_size];
+        for (id, component) in components.iter().enumerate() {
+            for &v in component {
+                self.groups[v as usize] = id as i32;
+            }
+        }
+        self
+    }
+
+    fn on_path(&self, v: usize, w: usize, directed: bool) -> bool {
+        self.path.windows(2).any(|pair| {
+            (pair[0] == v && pair[1] == w) || (!directed && pair[0] == w && pair[1] == v)
+        })
+    }
+}
+
+// 导出成 DOT 格式，所有底层表示都可以用
+pub fn to_dot<G: AdjIterable>(graph: &G, options: &DotOptions) -> String {
+    write_dot(graph, options, |_, _| None)
+}
+
+// 带权图导出时，把权值作为边的 label
+pub fn to_dot_weighted(graph: &WeightedGraph, options: &DotOptions) -> String {
+    write_dot(graph, options, |v, w| graph.weight(v, w))
+}
+
+fn write_dot<G: AdjIterable>(graph: &G, options: &DotOptions, weight: impl Fn(usize, usize) -> Option<f64>) -> String {
+    let directed = graph.is_directed();
+    let (kind, op) = if directed { ("digraph", "->") } else { ("graph", "--") };
+    let on_path: BTreeSet<usize> = options.path.iter().copied().collect();
+
+    let mut res = String::new();
+    // 名字总是加上引号，里面可以有空格和 - 这样的字符，引号本身要转义
+    let _ = writeln!(res, "{kind} \"{}\" {{", options.name.replace('"', "\\\""));
+
+    // 每个顶点都写一行，孤立的顶点也不会丢
+    for v in 0..graph.v() {
+        let mut attrs = vec![];
+        if let Some(&group) = options.groups.get(v) {
+            if group >= 0 {
+                attrs.push(format!("style=filled, fillcolor={}", PALETTE[group as usize % PALETTE.len()]));
+            }
+        }
+        if on_path.contains(&v) {
+            attrs.push("color=red".to_owned());
+        }
+        let _ = writeln!(res, "    {v}{};", format_attrs(&attrs));
+    }
+
+    // 无向图每条边只写一次
+    for v in 0..graph.v() {
+        for w in graph.adj(v) {
+            if !directed && w < v {
+                continue;
+            }
+
+            let mut attrs = vec![];
+            if let Some(weight) = weight(v, w) {
+                attrs.push(format!("label={weight}"));
+            }
+            if options.on_path(v, w, directed) {
+                attrs.push("color=red, penwidth=2".to_owned());
+            }
+            let _ = writeln!(res, "    {v} {op} {w}{};", format_attrs(&attrs));
+        }
+    }
+
+    res.push('}');
+    res
+}
+
+fn format_attrs(attrs: &[String]) -> String {
+    if attrs.is_empty() {
+        return String::new();
+    }
+    format!(" [{}]", attrs.join(", "))
+}
+
+// 读取 DOT 格式，支持 graph {} 和 digraph {} 中最常用的部分：
+// 顶点语句 `0 [...]`，边语句 `0 -- 1 -- 2 [label=3]`，顶点必须是非负整数，label 或 weight 属性作为权值
+// 其他的属性语句(node [...]、rankdir=LR 等)和 // # 开头的注释直接忽略
+// { } 和语句可以写在同一行，也可以分开写在多行，例如 `graph { 0 -- 1; }`
+pub fn from_dot<G: GraphBuilder>(s: &str) -> Result<G> {
+    let mut tokens = tokenize(s).into_iter();
+    // 没有写完就结束了，报告在最后一行
+    let eof = || InvalidDot(s.lines().count(), String::new());
+
+    // 开头：[strict] graph|digraph [name] {
+    let (line_no, header) = tokens.next().ok_or_else(eof)?;
+    let mut words = header.split_whitespace();
+    let mut kind = words.next().unwrap_or_default();
+    if kind == "strict" {
+        kind = words.next().unwrap_or_default();
+    }
+    let directed = match kind {
+        "graph" => false,
+        "digraph" => true,
+        _ => return Err(InvalidDot(line_no, header)),
+    };
+    match tokens.next() {
+        Some((_, token)) if token == "{" => {}
+        Some((line_no, token)) => return Err(InvalidDot(line_no, token)),
+        None => return Err(eof()),
+    }
+
+    let op = if directed { "->" } else { "--" };
+    let mut v_size = 0;
+    let mut edges = vec![];
+    let mut closed = false;
+
+    for (line_no, stmt) in tokens.by_ref() {
+        match stmt.as_str() {
+            "}" => {
+                closed = true;
+                break;
+            }
+            // 不支持子图
+            "{" => return Err(InvalidDot(line_no, stmt)),
+            _ => {}
+        }
+
+        // 分开语句和属性
+        let (body, attrs) = match stmt.find('[') {
+            Some(i) => (stmt[..i].trim(), stmt[i..].trim()),
+            None => (stmt.as_str(), ""),
+        };
+
+        // 属性语句
+        if matches!(body, "node" | "edge" | "graph") || (body.contains('=') && !body.contains(op)) {
+            continue;
+        }
+
+        let mut vertices = vec![];
+        for token in body.split(op) {
+            let v = usize::from_str(token.trim().trim_matches('"')).map_err(|_| InvalidDot(line_no, stmt.clone()))?;
+            v_size = v_size.max(v + 1);
+            vertices.push(v);
+        }
+
+        let weight = parse_weight(attrs);
+        for pair in vertices.windows(2) {
+            edges.push((line_no, pair[0], pair[1], weight));
+        }
+    }
+
+    if !closed {
+        return Err(eof());
+    }
+    // } 后面不能再有别的内容
+    if let Some((line_no, token)) = tokens.next() {
+        return Err(InvalidDot(line_no, token));
+    }
+
+    // 读完才知道顶点数，再直接构建图，自环边和平行边的错误带上所在的行号
+    let mut graph = if directed { G::with_vertices_directed(v_size) } else { G::with_vertices(v_size) };
+    for (line_no, v, w, weight) in edges {
+        graph.add_weighted_edge(v, w, weight).map_err(|err| err.at_line(line_no))?;
+    }
+    Ok(graph)
+}
+
+// 按 { } ; 和换行把文本切分成 (行号, 语句)，{ 和 } 单独作为一项，引号和 [] 中的内容不切分
+fn tokenize(s: &str) -> Vec<(usize, String)> {
+    let mut tokens = vec![];
+
+    for (line_no, line) in s.lines().enumerate() {
+        let line_no = line_no + 1;
+        let line = line.trim();
+        if line.starts_with("//") || line.starts_with('#') {
+            continue;
+        }
+
+        let mut stmt = String::new();
+        let mut quoted = false;
+        let mut escaped = false;
+        let mut depth = 0;
+        for c in line.chars() {
+            // 引号中 \ 后面的字符原样保留，\" 不会结束引号
+            if escaped {
+                escaped = false;
+                stmt.push(c);
+                continue;
+            }
+            match c {
+                '\\' if quoted => escaped = true,
+                '"' => quoted = !quoted,
+                '[' if !quoted => depth += 1,
+                ']' if !quoted => depth -= 1,
+                '{' | '}' | ';' if !quoted && depth == 0 => {
+                    push_stmt(&mut tokens, line_no, &mut stmt);
+                    if c != ';' {
+                        tokens.push((line_no, c.to_string()));
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+            stmt.push(c);
+        }
+        push_stmt(&mut tokens, line_no, &mut stmt);
+    }
+
+    tokens
+}
+
+fn push_stmt(tokens: &mut Vec<(usize, String)>, line_no: usize, stmt: &mut String) {
+    let trimmed = stmt.trim();
+    if !trimmed.is_empty() {
+        tokens.push((line_no, trimmed.to_owned()));
+    }
+    stmt.clear();
+}
+
+// 从 [label=3, color=red] 中取出 label 或 weight 的值，没有时权值为 1
+fn parse_weight(attrs: &str) -> f64 {
+    attrs.trim_matches(|c| c == '[' || c == ']')
+        .split(',')
+        .filter_map(|attr| attr.split_once('='))
+        .filter(|(key, _)| matches!(key.trim(), "label" | "weight"))
+        .find_map(|(_, value)| f64::from_str(value.trim().trim_matches('"')).ok())
+        .unwrap_or(1.0)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_matrix::AdjMatrix;
+    use crate::graph::bi_partition_detection::BiPartitionDetection;
+    use crate::graph::cc::CC;
+    use crate::graph::error::MatrixError;
+    use crate::graph::graph::Graph;
+    use crate::graph::path::Path;
+
+    fn assert_same<G: AdjIterable>(a: &G, b: &G) {
+        assert_eq!(a.v(), b.v());
+        assert_eq!(a.e(), b.e());
+        assert_eq!(a.is_directed(), b.is_directed());
+        for v in 0..a.v() {
+            assert_eq!(a.adj(v).collect::<Vec<_>>(), b.adj(v).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn round_trip_test() {
+        for file_path in ["g.txt", "g_bfs.txt", "g_bridges.txt"] {
+            let graph = Graph::from_file(file_path);
+            let dot = to_dot(&graph, &DotOptions::default());
+            assert_same(&graph, &from_dot::<Graph>(&dot).unwrap());
+        }
+
+        let graph = AdjMatrix::from_file_directed("g_directed.txt");
+        let dot = to_dot(&graph, &DotOptions::default());
+        assert!(dot.starts_with("digraph \"G\" {"));
+        assert!(dot.contains("    3 -> 2;"));
+        assert_same(&graph, &from_dot::<AdjMatrix>(&dot).unwrap());
+
+        // 名字中有空格、- 或者引号时写出来的 DOT 也是合法的，并且可以读回来
+        let options = DotOptions { name: "my graph".to_owned(), ..DotOptions::default() };
+        assert!(to_dot(&graph, &options).starts_with("digraph \"my graph\" {"));
+        for name in ["router-a", "say \"hi\"; {x}"] {
+            let options = DotOptions { name: name.to_owned(), ..DotOptions::default() };
+            let dot = to_dot(&graph, &options);
+            assert_same(&graph, &from_dot::<AdjMatrix>(&dot).unwrap());
+        }
+
+        let graph = WeightedGraph::from_file("g_weighted.txt");
+        let dot = to_dot_weighted(&graph, &DotOptions::default());
+        let res = from_dot::<WeightedGraph>(&dot).unwrap();
+        assert_same(&graph, &res);
+        assert_eq!(res.adj, graph.adj);
+    }
+
+    #[test]
+    fn options_test() {
+        let graph = Graph::from_file("g.txt");
+
+        let path = Path::new(&graph, 0, 6);
+        let cc = CC::new(&graph);
+
+        let options = DotOptions::new()
+            .highlight_path(path.path())
+            .color_components(&cc.components());
+        let dot = to_dot(&graph, &options);
+        assert!(dot.contains("    0 [style=filled, fillcolor=lightblue, color=red];"));
+        assert!(dot.contains("    5 [style=filled, fillcolor=lightgreen];"));
+        assert!(dot.contains("    2 -- 6 [color=red, penwidth=2];"));
+        assert!(dot.contains("    1 -- 4;"));
+
+        let bp = BiPartitionDetection::new(&graph);
+        let dot = to_dot(&graph, &DotOptions::new().color_groups(bp.colors().to_vec()));
+        assert!(dot.contains("    1 [style=filled, fillcolor=lightgreen];"));
+        assert!(dot.contains("    3 [style=filled, fillcolor=lightblue];"));
+    }
+
+    #[test]
+    fn read_dot_test() {
+        let dot = "// 注释\nstrict digraph \"test\" {\n  node [shape=circle]; rankdir=LR\n  0 -> 1 -> 2 [weight=2.5];\n  \"3\"\n}";
+        let graph = from_dot::<WeightedGraph>(dot).unwrap();
+        assert!(graph.is_directed());
+        assert_eq!(graph.v(), 4);
+        assert_eq!(graph.weight(1, 2), Some(2.5));
+        assert_eq!(graph.weight(1, 0), None);
+
+        match from_dot::<Graph>("graph {\n  0 -- a;\n}") {
+            Err(MatrixError::InvalidDot(line, text)) => {
+                assert_eq!(line, 2);
+                assert_eq!(text, "0 -- a");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert!(matches!(from_dot::<Graph>("tree {\n}"), Err(MatrixError::InvalidDot(1, _))));
+        assert!(matches!(from_dot::<Graph>("graph {\n  0 -- 1;\n"), Err(MatrixError::InvalidDot(2, _))));
+        assert!(matches!(from_dot::<Graph>("graph { 0 -- 1 }\n2 -- 3"), Err(MatrixError::InvalidDot(2, _))));
+        assert!(matches!(from_dot::<Graph>(""), Err(MatrixError::InvalidDot(0, _))));
+
+        // 平行边和自环边带上所在的行号
+        assert!(matches!(from_dot::<Graph>("graph {\n  0 -- 1;\n\n  1 -- 0;\n}"), Err(MatrixError::ParallelEdge(4))));
+        assert!(matches!(from_dot::<Graph>("digraph {\n  0 -> 1 -> 1\n}"), Err(MatrixError::SelfLoop(2))));
+    }
+
+    #[test]
+    fn layout_test() {
+        // { } 和语句写在同一行或者分开写都可以
+        let layouts = [
+            "graph { 0 -- 1; 1 -- 2 }",
+            "graph G\n{\n  0 -- 1\n  1 -- 2 }",
+            "graph {\n  0 -- 1; 1 -- 2;\n}",
+            "graph { node [shape=\"circle;box\"]\n0 -- 1 [label=\"a;b\"]; 1 -- 2 }",
+        ];
+        for dot in layouts {
+            let graph = from_dot::<Graph>(dot).unwrap();
+            assert_eq!((graph.v(), graph.e()), (3, 2));
+            assert!(graph.has_edge(1, 2));
+        }
+
+        // 空图
+        let graph = from_dot::<Graph>("graph {}").unwrap();
+        assert_eq!((graph.v(), graph.e()), (0, 0));
+        let graph = from_dot::<WeightedGraph>("digraph G {\n}").unwrap();
+        assert!(graph.is_directed());
+        assert_eq!(graph.v(), 0);
+    }
+}
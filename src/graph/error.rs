@@ -1,5 +1,6 @@
-use std::{fmt, io, result};
+use std::result;
 use thiserror::Error;
+use MatrixError::*;
 
 pub type Result<T> = result::Result<T, MatrixError>;
 
@@ -19,10 +20,88 @@ pub enum MatrixError {
     #[error("vertex: {0} must smaller than {1} ")]
     InvalidVertexEdge(usize, usize),
 
-    // 自环边错误
-    #[error("self loop detected")]
-    SelfLoop,
+    // 第 {0} 行是自环边，行号为 0 表示不是从文本读到的边(比如 GraphBuilder::add_edge)
+    #[error("line {0}: self loop detected")]
+    SelfLoop(usize),
 
-    #[error("parallel edge detected")]
-    ParallelEdge
+    // 第 {0} 行是平行边，行号的含义和 SelfLoop 一样
+    #[error("line {0}: parallel edge detected")]
+    ParallelEdge(usize),
+
+    // 权值不是合法的数字
+    #[error("line {0}: invalid weight `{1}`")]
+    InvalidWeight(usize, String),
+
+    // Dijkstra 不能处理负权边
+    #[error("negative weight {2} on edge {0}-{1}")]
+    NegativeWeight(usize, usize, f64),
+
+    // 0-1 bfs 只能处理权值为 0 或 1 的边
+    #[error("weight {2} on edge {0}-{1} is neither 0 nor 1")]
+    NotZeroOneWeight(usize, usize, f64),
+
+    // 最大流的源点和汇点不能是同一个顶点
+    #[error("source and sink are the same vertex {0}")]
+    SourceIsSink(usize),
+
+    // 最小生成树等算法只能用于无向图
+    #[error("undirected graph required")]
+    DirectedGraph,
+
+    // 拓扑排序等算法只能用于有向图
+    #[error("directed graph required")]
+    UndirectedGraph,
+
+    // 有向图中有环，不是 DAG，带上找到的环
+    #[error("graph is not a DAG, found cycle {0:?}")]
+    NotDag(Vec<usize>),
+
+    // 状态压缩的算法顶点数不能太多
+    #[error("too many vertices: {0}, at most {1} supported")]
+    TooManyVertices(usize, usize),
+
+    // 匹配等算法只能用于二分图，带上找到的奇数长度的环
+    #[error("graph is not bipartite, found odd cycle {0:?}")]
+    NotBipartite(Vec<usize>),
+
+    // 符号图中没有这个名字的顶点
+    #[error("unknown vertex `{0}`")]
+    UnknownVertex(String),
+
+    // 第 {0} 行的 `{1}` 不是合法的非负整数
+    #[error("line {0}: invalid number `{1}`")]
+    InvalidNumber(usize, String),
+
+    // 第 {0} 行缺少列，带上整行内容
+    #[error("line {0}: missing column in `{1}`")]
+    MissingColumn(usize, String),
+
+    // 第 {0} 行在权值后面还有多余的列，带上整行内容
+    #[error("line {0}: extra column in `{1}`")]
+    ExtraColumn(usize, String),
+
+    // 第 {0} 行的顶点 {1} 不小于顶点数 {2}
+    #[error("line {0}: vertex {1} must smaller than {2}")]
+    VertexOutOfRange(usize, usize, usize),
+
+    // 第一行声明的边数和实际读到的边数不一致
+    #[error("header declares {0} edges but {1} found")]
+    EdgeCountMismatch(usize, usize),
+
+    // DOT 文件第 {0} 行无法解析
+    #[error("line {0}: invalid dot statement `{1}`")]
+    InvalidDot(usize, String),
 }
+
+impl MatrixError {
+    // GraphBuilder 添加边时不知道行号，从文本构建图时用它把行号补上
+    pub fn at_line(self, line_no: usize) -> Self {
+        match self {
+            SelfLoop(_) => SelfLoop(line_no),
+            ParallelEdge(_) => ParallelEdge(line_no),
+            InvalidWeight(_, token) => InvalidWeight(line_no, token),
+            InvalidVertexEdge(v, n) => VertexOutOfRange(line_no, v, n),
+            err => err,
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,243 @@
+use std::collections::BTreeSet;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::cc::CC;
+use crate::graph::graph::Graph;
+
+// 欧拉回路和欧拉路径：每条边恰好经过一次
+// 无向图：有边的顶点都联通，所有顶点的度都是偶数时有欧拉回路，恰好两个顶点的度是奇数时有欧拉路径
+// 有向图：有边的顶点弱联通，所有顶点入度等于出度时有欧拉回路，
+//        恰好一个顶点出度比入度大 1 (起点)、一个顶点入度比出度大 1 (终点) 时有欧拉路径
+#[derive(Debug, Clone)]
+pub struct Euler<'a, G> {
+    graph: &'a G,
+    // 有向图每个顶点 出度 - 入度，无向图是每个顶点的度
+    degrees: Vec<i64>,
+    connected: bool,
+    // 欧拉路径的顶点序列，没有欧拉路径时为空
+    path: Vec<usize>,
+}
+
+impl<'a, G: AdjIterable> Euler<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        let mut degrees = vec![0; graph.v()];
+        for v in 0..graph.v() {
+            for w in graph.adj(v) {
+                degrees[v] += 1;
+                if graph.is_directed() {
+                    degrees[w] -= 1;
+                }
+            }
+        }
+
+        let mut euler = Self {
+            graph,
+            degrees,
+            connected: Self::is_connected(graph),
+            path: vec![],
+        };
+        if let Some(s) = euler.path_start() {
+            euler.path = euler.hierholzer(s);
+        }
+        euler
+    }
+
+    // 有边的顶点是否都在同一个联通分量中，有向图忽略边的方向
+    fn is_connected(graph: &G) -> bool {
+        let mut has_edge = vec![false; graph.v()];
+        for v in 0..graph.v() {
+            for w in graph.adj(v) {
+                has_edge[v] = true;
+                has_edge[w] = true;
+            }
+        }
+
+        if !graph.is_directed() {
+            return Self::in_one_component(&CC::new(graph), &has_edge);
+        }
+
+        // 有向图先构建一个无向的视图，再用 CC 求联通分量
+        let mut adj: Vec<BTreeSet<usize>> = (0..graph.v()).map(|_| BTreeSet::new()).collect();
+        for v in 0..graph.v() {
+            for w in graph.adj(v) {
+                adj[v].insert(w);
+                adj[w].insert(v);
+            }
+        }
+        let e = adj.iter().map(|set| set.len()).sum::<usize>() / 2;
+        let undirected = Graph { v: graph.v(), e, adj, directed: false };
+        Self::in_one_component(&CC::new(&undirected), &has_edge)
+    }
+
+    fn in_one_component<H: AdjIterable>(cc: &CC<H>, has_edge: &[bool]) -> bool {
+        let ids = cc.ids();
+        let mut vertices = (0..ids.len()).filter(|&v| has_edge[v]);
+        match vertices.next() {
+            Some(first) => vertices.all(|v| ids[v] == ids[first]),
+            None => true,
+        }
+    }
+
+    pub fn has_euler_circuit(&self) -> bool {
+        if !self.connected {
+            return false;
+        }
+
+        let directed = self.graph.is_directed();
+        self.degrees.iter().all(|&d| if directed { d == 0 } else { d % 2 == 0 })
+    }
+
+    // 欧拉路径的起点，没有欧拉路径返回 None。有欧拉回路时从第一个有出边的顶点出发
+    fn path_start(&self) -> Option<usize> {
+        if !self.connected {
+            return None;
+        }
+
+        let g = self.graph;
+        let degrees = &self.degrees;
+        let first = (0..g.v()).find(|&v| g.adj(v).next().is_some()).unwrap_or(0);
+
+        if g.is_directed() {
+            let starts: Vec<usize> = (0..g.v()).filter(|&v| degrees[v] == 1).collect();
+            let ends = (0..g.v()).filter(|&v| degrees[v] == -1).count();
+            let others = (0..g.v()).filter(|&v| degrees[v].abs() > 1).count();
+            match (starts.len(), ends, others) {
+                (0, 0, 0) => Some(first),
+                (1, 1, 0) => Some(starts[0]),
+                _ => None,
+            }
+        } else {
+            let odds: Vec<usize> = (0..g.v()).filter(|&v| degrees[v] % 2 == 1).collect();
+            match odds.len() {
+                0 => Some(first),
+                2 => Some(odds[0]),
+                _ => None,
+            }
+        }
+    }
+
+    // 有欧拉回路时也一定有欧拉路径
+    pub fn has_euler_path(&self) -> bool {
+        self.path_start().is_some()
+    }
+
+    // 欧拉回路的顶点序列，首尾是同一个顶点，没有欧拉回路时返回空
+    pub fn euler_circuit(&self) -> &[usize] {
+        if !self.has_euler_circuit() {
+            return &[];
+        }
+        &self.path
+    }
+
+    // 欧拉路径的顶点序列，没有欧拉路径时返回空
+    pub fn euler_path(&self) -> &[usize] {
+        &self.path
+    }
+
+    // Hierholzer 算法：沿着没走过的边一直走，走不动了就把当前顶点加入结果并回退
+    fn hierholzer(&self, s: usize) -> Vec<usize> {
+        let g = self.graph;
+        if g.e() == 0 {
+            return vec![];
+        }
+
+        // 在邻接表的拷贝上删边，不修改原图
+        let mut adj: Vec<BTreeSet<usize>> = (0..g.v()).map(|v| g.adj(v).collect()).collect();
+        let mut res = vec![];
+        let mut stack = vec![s];
+
+        while let Some(&v) = stack.last() {
+            if let Some(&w) = adj[v].iter().next() {
+                adj[v].remove(&w);
+                if !g.is_directed() {
+                    adj[w].remove(&v);
+                }
+                stack.push(w);
+            } else {
+                res.push(v);
+                stack.pop();
+            }
+        }
+
+        res.reverse();
+        res
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_list::AdjList;
+
+    // 检查顶点序列中相邻的两个顶点之间有边，并且每条边恰好用了一次
+    fn check_path(graph: &Graph, path: &[usize]) {
+        assert_eq!(path.len(), graph.e + 1);
+        let mut used = BTreeSet::new();
+        for pair in path.windows(2) {
+            let (v, w) = (pair[0], pair[1]);
+            assert!(graph.has_edge(v, w));
+            let key = if graph.directed { (v, w) } else { (v.min(w), v.max(w)) };
+            assert!(used.insert(key));
+        }
+    }
+
+    #[test]
+    fn circuit_test() {
+        let graph = Graph::from_file("g_euler.txt");
+        let euler = Euler::new(&graph);
+        assert!(euler.has_euler_circuit());
+        assert!(euler.has_euler_path());
+
+        let circuit = euler.euler_circuit();
+        check_path(&graph, circuit);
+        assert_eq!(circuit.first(), circuit.last());
+        assert_eq!(Euler::new(&AdjList::from_file("g_euler.txt")).euler_circuit(), circuit);
+
+        // g.txt 有 4 个度为奇数的顶点
+        let graph = Graph::from_file("g.txt");
+        let euler = Euler::new(&graph);
+        assert!(!euler.has_euler_circuit());
+        assert!(!euler.has_euler_path());
+        assert!(euler.euler_path().is_empty());
+    }
+
+    #[test]
+    fn path_test() {
+        let mut graph = Graph::new();
+        graph.read_data("5 5\n0 1\n0 2\n1 2\n2 3\n3 4").unwrap();
+
+        let euler = Euler::new(&graph);
+        assert!(!euler.has_euler_circuit());
+        let path = euler.euler_path();
+        check_path(&graph, path);
+        assert_eq!(path[0], 2);
+        assert_eq!(path[path.len() - 1], 4);
+
+        // 两个联通分量都有边
+        let mut graph = Graph::new();
+        graph.read_data("6 6\n0 1\n1 2\n2 0\n3 4\n4 5\n5 3").unwrap();
+        assert!(!Euler::new(&graph).has_euler_path());
+    }
+
+    #[test]
+    fn directed_test() {
+        let mut graph = Graph::new_directed();
+        graph.read_data("4 5\n0 1\n1 2\n2 0\n0 3\n3 0").unwrap();
+        let euler = Euler::new(&graph);
+        assert!(euler.has_euler_circuit());
+        check_path(&graph, euler.euler_circuit());
+
+        let mut graph = Graph::new_directed();
+        graph.read_data("4 4\n0 1\n1 2\n2 0\n2 3").unwrap();
+        let euler = Euler::new(&graph);
+        assert!(!euler.has_euler_circuit());
+        let path = euler.euler_path();
+        check_path(&graph, path);
+        assert_eq!(path, [2, 0, 1, 2, 3]);
+
+        // g_dag.txt 中 3 -> 2、3 -> 4，出入度差不满足条件
+        let graph = Graph::from_file_directed("g_dag.txt");
+        let euler = Euler::new(&graph);
+        assert!(!euler.has_euler_path());
+    }
+}
@@ -0,0 +1,81 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 判断剩余容量时忽略浮点误差
+pub const EPS: f64 = 1e-9;
+
+// 网络中的一条边，反向边和它成对存放：编号为 i 的边，反向边编号是 i ^ 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowEdge {
+    pub from: usize,
+    pub to: usize,
+    pub cap: f64,
+    pub flow: f64,
+}
+
+impl FlowEdge {
+    // 剩余容量，反向边的容量是 0，流过的流量越多，反向边的剩余容量越大
+    pub fn residual(&self) -> f64 {
+        self.cap - self.flow
+    }
+}
+
+// 网络流的图，同时也是残量图：每条边 v -> w 都有一条容量为 0 的反向边 w -> v
+#[derive(Debug, Clone)]
+pub struct FlowNetwork {
+    pub v: usize,
+    pub edges: Vec<FlowEdge>,
+    // adj[v] 是从 v 出发的边(包括反向边)在 edges 中的编号
+    pub adj: Vec<Vec<usize>>,
+}
+
+impl FlowNetwork {
+    pub fn new(v: usize) -> Self {
+        Self {
+            v,
+            edges: vec![],
+            adj: vec![vec![]; v],
+        }
+    }
+
+    // 从带权图文件构建，权值就是容量，每一行 v w cap 表示 v -> w 的边
+    pub fn from_file(file_path: &str) -> Self {
+        Self::from_graph(&WeightedGraph::from_file_directed(file_path))
+    }
+
+    // 无向图的每条边看成两个方向各一条容量相同的边
+    pub fn from_graph(graph: &WeightedGraph) -> Self {
+        let mut network = Self::new(graph.v());
+        for v in 0..graph.v() {
+            for (w, cap) in graph.adj_weighted(v) {
+                network.add_edge(v, w, cap);
+            }
+        }
+        network
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: f64) {
+        self.adj[from].push(self.edges.len());
+        self.edges.push(FlowEdge { from, to, cap, flow: 0.0 });
+        self.adj[to].push(self.edges.len());
+        self.edges.push(FlowEdge { from: to, to: from, cap: 0.0, flow: 0.0 });
+    }
+
+    // 沿着编号为 i 的边推送 f 的流量，反向边的流量相应减少
+    pub fn push_flow(&mut self, i: usize, f: f64) {
+        self.edges[i].flow += f;
+        self.edges[i ^ 1].flow -= f;
+    }
+
+    // 清空所有流量
+    pub fn reset(&mut self) {
+        for edge in self.edges.iter_mut() {
+            edge.flow = 0.0;
+        }
+    }
+
+    // 原图中的边(不包括反向边)
+    pub fn original_edges(&self) -> impl Iterator<Item = &FlowEdge> {
+        self.edges.iter().step_by(2)
+    }
+}
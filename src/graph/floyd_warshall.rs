@@ -0,0 +1,209 @@
+use std::io;
+use std::io::Write;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::adj_matrix::AdjMatrix;
+use crate::graph::error::{MatrixError::PrintMatrixError, Result};
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 所有点对的最短路径，直接在 V*V 的距离矩阵上做动态规划，构造的时候就完成计算
+#[derive(Debug, Clone)]
+pub struct FloydWarshall {
+    v: usize,
+    // dis[v][w] 是 v 到 w 的最短距离，不可达为无穷大
+    dis: Vec<Vec<f64>>,
+    // next[v][w] 是从 v 到 w 的最短路径上 v 的下一个顶点，不可达为 -1
+    next: Vec<Vec<i32>>,
+    has_negative_cycle: bool,
+}
+
+impl FloydWarshall {
+    pub fn new(graph: &WeightedGraph) -> Self {
+        let mut fw = Self::with_v(graph.v());
+        for v in 0..graph.v() {
+            for (w, weight) in graph.adj_weighted(v) {
+                fw.dis[v][w] = weight;
+                fw.next[v][w] = w as i32;
+            }
+        }
+        fw.process();
+        fw
+    }
+
+    // 邻接矩阵中为 1 的位置就是一条权值为 1 的边
+    pub fn from_matrix(matrix: &AdjMatrix) -> Self {
+        let mut fw = Self::with_v(matrix.v());
+        for (v, row) in matrix.adj.iter().enumerate() {
+            for (w, &x) in row.iter().enumerate() {
+                if x == 1 {
+                    fw.dis[v][w] = 1.0;
+                    fw.next[v][w] = w as i32;
+                }
+            }
+        }
+        fw.process();
+        fw
+    }
+
+    // 初始时每个顶点到自己的距离是 0，到其它顶点是无穷大
+    fn with_v(v: usize) -> Self {
+        let mut dis = vec![vec![f64::INFINITY; v]; v];
+        let mut next = vec![vec![-1; v]; v];
+        for i in 0..v {
+            dis[i][i] = 0.0;
+            next[i][i] = i as i32;
+        }
+
+        Self {
+            v,
+            dis,
+            next,
+            has_negative_cycle: false,
+        }
+    }
+
+    fn process(&mut self) {
+        let (dis, next) = (&mut self.dis, &mut self.next);
+
+        // 每一轮允许路径经过顶点 t 中转
+        for t in 0..self.v {
+            for v in 0..self.v {
+                if dis[v][t] == f64::INFINITY {
+                    continue;
+                }
+                for w in 0..self.v {
+                    if dis[v][t] + dis[t][w] < dis[v][w] {
+                        dis[v][w] = dis[v][t] + dis[t][w];
+                        next[v][w] = next[v][t];
+                    }
+                }
+            }
+        }
+
+        // 自己到自己的距离变成负数，说明这个顶点在负权环上
+        self.has_negative_cycle = (0..self.v).any(|v| dis[v][v] < 0.0);
+    }
+
+    pub fn has_negative_cycle(&self) -> bool {
+        self.has_negative_cycle
+    }
+
+    pub fn is_connected(&self, v: usize, w: usize) -> bool {
+        self.dis[v][w] != f64::INFINITY
+    }
+
+    pub fn dis(&self, v: usize, w: usize) -> f64 {
+        self.dis[v][w]
+    }
+
+    // 从 v 到 w 的最短路径上，v 后面的那个顶点
+    pub fn next_hop(&self, v: usize, w: usize) -> Option<usize> {
+        let hop = self.next[v][w];
+        if hop < 0 {
+            None
+        } else {
+            Some(hop as usize)
+        }
+    }
+
+    pub fn dis_matrix(&self) -> &[Vec<f64>] {
+        &self.dis
+    }
+
+    pub fn next_matrix(&self) -> &[Vec<i32>] {
+        &self.next
+    }
+
+    // 沿着 next 矩阵从 v 一直走到 w，有负权环时最短路径不存在，返回空
+    pub fn path(&self, v: usize, w: usize) -> Vec<usize> {
+        let mut res = vec![];
+        if self.has_negative_cycle() || !self.is_connected(v, w) {
+            return res;
+        }
+
+        let mut cur = v;
+        res.push(cur);
+        while cur != w {
+            cur = self.next[cur][w] as usize;
+            res.push(cur);
+        }
+
+        res
+    }
+
+    // 和 AdjMatrix::print_adj 一样，每一行输出一个顶点到所有顶点的距离
+    pub fn print_dis(&self) -> Result<()> {
+        let mut buffer = io::BufWriter::new(io::stdout());
+        self.write_dis(&mut buffer)?;
+        buffer.flush().map_err(|_| PrintMatrixError)?;
+
+        Ok(())
+    }
+
+    pub fn write_dis<W: Write>(&self, buffer: &mut W) -> Result<()> {
+        if self.dis.is_empty() {
+            return Err(PrintMatrixError);
+        }
+
+        for row in self.dis.iter() {
+            for x in row {
+                write!(buffer, "{} ", x).map_err(|_| PrintMatrixError)?;
+            }
+            writeln!(buffer).map_err(|_| PrintMatrixError)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dijkstra::Dijkstra;
+
+    #[test]
+    fn floyd_test() {
+        let graph = WeightedGraph::from_file("g_weighted.txt");
+        let fw = FloydWarshall::new(&graph);
+        let _ = fw.print_dis();
+
+        assert!(!fw.has_negative_cycle());
+        // 每一行都要和以该顶点为源的 Dijkstra 结果一致
+        for s in 0..5 {
+            let dijkstra = Dijkstra::new(&graph, s).unwrap();
+            for t in 0..5 {
+                assert_eq!(fw.dis(s, t), dijkstra.dis(t));
+            }
+        }
+
+        assert_eq!(fw.path(0, 3), vec![0, 2, 1, 3]);
+        assert_eq!(fw.next_hop(0, 3), Some(2));
+        assert_eq!(fw.path(2, 2), vec![2]);
+    }
+
+    #[test]
+    fn matrix_test() {
+        let matrix = AdjMatrix::from_file("g.txt");
+        let fw = FloydWarshall::from_matrix(&matrix);
+
+        assert_eq!(fw.dis(0, 6), 2.0);
+        assert_eq!(fw.path(0, 6), vec![0, 2, 6]);
+        assert!(!fw.is_connected(0, 5));
+        assert!(fw.path(0, 5).is_empty());
+        assert_eq!(fw.next_hop(0, 5), None);
+
+        let mut out = Vec::new();
+        fw.write_dis(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert_eq!(out.lines().next(), Some("0 1 1 2 2 inf 2 "));
+    }
+
+    #[test]
+    fn negative_cycle_test() {
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("4 4\n0 1 1\n1 2 -2\n2 1 1\n2 3 1").unwrap();
+
+        let fw = FloydWarshall::new(&graph);
+        assert!(fw.has_negative_cycle());
+        assert!(fw.path(0, 3).is_empty());
+    }
+}
@@ -1,8 +1,10 @@
-use std::collections::BTreeSet;
-use std::{fs, io, usize};
+use std::collections::{btree_set, BTreeSet};
+use std::io;
 use std::io::Write;
-use std::str::FromStr;
-use crate::graph::error::MatrixError::{InvalidVertexEdge, ParallelEdge, PrintMatrixError, ReadFileError, SelfLoop, VertexError};
+use std::iter::Copied;
+use crate::graph::adj_iterable::{AdjIterable, Edges};
+use crate::graph::graph_builder::GraphBuilder;
+use crate::graph::error::MatrixError::PrintMatrixError;
 use crate::graph::error::Result;
 
 
@@ -11,6 +13,7 @@ pub struct Graph {
     pub v: usize,
     pub e: usize,
     pub adj: Vec<BTreeSet<usize>>,
+    pub directed: bool,
 }
 
 impl Graph {
@@ -19,142 +22,236 @@ impl Graph {
         Self {
             v: 0,
             e: 0,
-            adj: Default::default()
+            adj: Default::default(),
+            directed: false,
         }
     }
 
-    pub fn init_matrix(&mut self, file_path: &str)  {
-        let _ = Graph::read_file(file_path)
-            .map(|content| {
-                self.read_data(&content).unwrap_or_default()
-            });
+    // 有向图
+    pub fn new_directed() -> Self {
+        Self {
+            directed: true,
+            ..Graph::new()
+        }
     }
 
+    pub fn print_adj(&self, adj: Vec<BTreeSet<usize>>) -> Result<()> {
+        if adj.is_empty() {
+            return Err(PrintMatrixError);
+        }
+
+        let mut buffer = io::BufWriter::new(io::stdout());
 
-    // 校验读到的数据，从第二行开始，第一列不能大于顶点数
-    pub fn validate_vertex(&self, v: usize) -> Result<()> {
-        // v是第一行读到的总顶点数，num1是从第二行开始读到的顶点编号
-        if v < 0 || v >= self.v {
-            return Err(InvalidVertexEdge(v, self.v));
+        // 顶点使用v, w这些名称，索引用i, j会更加清晰
+        for (v, set) in adj.iter().enumerate() {
+            writeln!(buffer, "vertex:{v} --> {:?} ", set).map_err(|_| PrintMatrixError)?;
         }
+
+        buffer.flush().map_err(|_| PrintMatrixError)?;
+
         Ok(())
     }
+}
 
+impl AdjIterable for Graph {
+    type Iter<'a> = Copied<btree_set::Iter<'a, usize>>;
 
-    pub fn read_file(file_path: &str) -> Result<String> {
-        let contents = fs::read_to_string(file_path)
-            .map_err(|_| ReadFileError)
-            .and_then(|s| Ok::<String, _>(s.trim().to_owned()))?;
-
-        Ok(contents)
+    fn v(&self) -> usize {
+        self.v
     }
 
-    // 只读取第一行，(顶点，边数)
-    pub fn read_data<S: AsRef<str>>(&mut self, s: S) -> Result<(usize, usize, Vec<BTreeSet<usize>>)> {
-        let s = s.as_ref();
-        let mut v = 0;
-        let mut e = 0;
-
-        if let Some(line) = s.lines().next() {
-            let mut iter = line.split_whitespace();
-            v = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-            e = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-        }
+    fn e(&self) -> usize {
+        self.e
+    }
 
-        if v <= 0 {
-            return Err(VertexError);
-        }
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
 
-        self.v = v;
-        self.e = e;
+    // 返回和某个顶点相连的边(只要找到相邻的点，就找到对应的边)，即返回相连的顶点的集合
+    fn adj(&self, v: usize) -> Self::Iter<'_> {
+        // 校验v是否合法
+        let _ = self.validate_vertex(v);
 
-        let mut adj: Vec<BTreeSet<usize>> = (0..v).map(|_| BTreeSet::new()).collect();
+        self.adj[v].iter().copied()
+    }
 
-        // 读取第一行后面的数据
-        let mut num1 = 0;
-        let mut num2 = 0;
-        // 从第二行开始读取，并赋值给二维数组
-        let mut s =  s.lines().skip(1);
-        while let Some(line) = s.next() {
-            let mut iter = line.split_whitespace();
-            num1 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-            num2 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
-
-            // 遇到平行边(将要处理的边在前面已经存储过了就是平行边)
-            if adj[num1].contains(&num2) {
-                return Err(ParallelEdge);
-            }
+    // 传入两个顶点
+    fn has_edge(&self, v: usize, w: usize) -> bool {
+        // 对传入的顶点校验
+        let _ = self.validate_vertex(v);
+        let _ = self.validate_vertex(w);
 
-            // 遇到自环边
-            if num1 == num2 {
-                return Err(SelfLoop);
-            }
+        self.adj[v].contains(&w)
+    }
 
-            if let Ok(_) = self.validate_vertex(num1) {
-                adj[num1].insert(num2);
+    // 把读到的边存到自己的结构里
+    fn from_edge_list(v: usize, e: usize, edges: Edges, directed: bool) -> Self {
+        let mut adj: Vec<BTreeSet<usize>> = (0..v).map(|_| BTreeSet::new()).collect();
+        for (num1, num2, _) in edges {
+            adj[num1].insert(num2);
+            // 无向图两个方向都要存
+            if !directed {
                 adj[num2].insert(num1);
             }
         }
 
-        self.adj = adj.clone();
-
-
-        Ok((v as usize, e as usize, adj))
+        Self {
+            v,
+            e,
+            adj,
+            directed,
+        }
     }
 
-    pub fn print_adj(&self, mut adj: Vec<BTreeSet<usize>>) -> Result<()> {
-        if adj.len() == 0 {
-            return Err(PrintMatrixError);
+    // 把每条边 v -> w 存成 w -> v
+    fn reverse(&self) -> Self {
+        if !self.directed {
+            return self.clone();
         }
 
-        let mut buffer = io::BufWriter::new(io::stdout());
-
-        // 顶点使用v, w这些名称，索引用i, j会更加清晰
-        for v in 0..adj.len() {
-            writeln!(buffer, "vertex:{v} --> {:?} ", adj[v].iter().map(|w| w)).map_err(|_| PrintMatrixError)?;
+        let mut adj: Vec<BTreeSet<usize>> = (0..self.v).map(|_| BTreeSet::new()).collect();
+        for (v, list) in self.adj.iter().enumerate() {
+            for &w in list {
+                adj[w].insert(v);
+            }
         }
 
-        buffer.flush().map_err(|_| PrintMatrixError)?;
+        Self {
+            v: self.v,
+            e: self.e,
+            adj,
+            directed: self.directed,
+        }
+    }
 
-        Ok(())
+    // 顶点的度，集合直接取长度
+    fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
     }
+}
 
-    // 传入两个顶点
-    pub fn has_edge(&self, v: usize, w: usize) -> bool {
-        // 对传入的顶点校验
-        let _ = self.validate_vertex(v);
-        let _ = self.validate_vertex(w);
 
-        self.adj[v].contains(&w)
+impl GraphBuilder for Graph {
+    fn with_vertices(n: usize) -> Self {
+        Self {
+            v: n,
+            adj: (0..n).map(|_| BTreeSet::new()).collect(),
+            ..Graph::new()
+        }
     }
 
-    // 返回和某个顶点相连的边(只要找到相邻的点，就找到对应的边)，即返回相连的顶点的集合
-    pub fn adj(&self, v: usize) -> &BTreeSet<usize> {
-        // 校验v是否合法
-        let _ = self.validate_vertex(v);
+    fn with_vertices_directed(n: usize) -> Self {
+        Self {
+            directed: true,
+            ..Graph::with_vertices(n)
+        }
+    }
 
-        // 直接返回顶点v对应的链表
-        return &self.adj[v]
+    fn add_vertex(&mut self) -> usize {
+        self.adj.push(BTreeSet::new());
+        self.v += 1;
+        self.v - 1
     }
 
-    // 顶点的度(顶点的邻边的个数)
-    fn degree(&self, v: usize) -> usize {
-        self.adj(v).len()
+    fn insert_edge(&mut self, v: usize, w: usize) {
+        self.adj[v].insert(w);
+        if !self.directed {
+            self.adj[w].insert(v);
+        }
+        self.e += 1;
     }
-}
 
+    fn remove_edge(&mut self, v: usize, w: usize) -> bool {
+        if v >= self.v || w >= self.v || !self.adj[v].remove(&w) {
+            return false;
+        }
+        if !self.directed {
+            self.adj[w].remove(&v);
+        }
+        self.e -= 1;
+        true
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::error::MatrixError;
 
     #[test]
     fn basic_test() {
         let mut graph = Graph::new();
-        graph.init_matrix("g_test.txt");
+        graph.init_matrix("g_test.txt").unwrap();
 
         println!("{:?}", graph.v);
         println!("{:?}", graph.e);
         println!("{:?}", graph.adj);
     }
+
+    #[test]
+    fn directed_test() {
+        let graph = Graph::from_file_directed("g_directed.txt");
+
+        assert!(graph.is_directed());
+        assert_eq!(graph.e(), 6);
+        assert!(graph.has_edge(1, 2));
+        assert!(!graph.has_edge(2, 1));
+
+        assert_eq!(graph.out_degree(1), 2);
+        assert_eq!(graph.in_degree(1), 1);
+        assert_eq!(graph.in_degree(2), 2);
+        assert_eq!(graph.in_degree(0), 0);
+
+        // 反图中入度和出度互换
+        let rev = graph.reverse();
+        assert!(rev.has_edge(2, 1));
+        assert!(!rev.has_edge(1, 2));
+        for v in 0..graph.v() {
+            assert_eq!(rev.in_degree(v), graph.out_degree(v));
+            assert_eq!(rev.out_degree(v), graph.in_degree(v));
+        }
+    }
+
+    #[test]
+    fn parse_error_test() {
+        // 每种错误都带上行号，行号从 1 开始数
+        let cases: Vec<(&str, MatrixError)> = vec![
+            ("x 2\n0 1\n1 2", MatrixError::InvalidNumber(1, "x".to_owned())),
+            ("3\n0 1", MatrixError::MissingColumn(1, "3".to_owned())),
+            ("3 2\n0 1\n1 b", MatrixError::InvalidNumber(3, "b".to_owned())),
+            ("3 2\n0 1\n2", MatrixError::MissingColumn(3, "2".to_owned())),
+            ("3 2\n0 3\n1 2", MatrixError::VertexOutOfRange(2, 3, 3)),
+            ("3 3\n0 1\n1 2", MatrixError::EdgeCountMismatch(3, 2)),
+            ("3 2\n0 1\n\n2 2", MatrixError::SelfLoop(4)),
+            ("3 2\n0 1\n1 0", MatrixError::ParallelEdge(3)),
+            ("3 1\n0 1 5 junk more", MatrixError::ExtraColumn(2, "0 1 5 junk more".to_owned())),
+        ];
+
+        for (data, expected) in cases {
+            let err = Graph::from_data(data, false).unwrap_err();
+            assert_eq!(format!("{err:?}"), format!("{expected:?}"));
+        }
+
+        assert!(matches!(Graph::try_from_file("not_exist.txt"), Err(MatrixError::ReadFileError)));
+        let empty = Graph::from_file_directed("not_exist.txt");
+        assert_eq!((empty.v(), empty.e(), empty.is_directed()), (0, 0, true));
+        assert_eq!(Graph::try_from_file("g.txt").unwrap().e(), 6);
+    }
+
+    #[test]
+    fn lenient_test() {
+        let (graph, warnings) = Graph::from_data_lenient("4 6\n0 1\n1 x\n\n1 9\n2 2\n1 0\n2 3\n1 3 2 x", false).unwrap();
+
+        assert_eq!(graph.e(), 2);
+        assert!(graph.has_edge(1, 0));
+        assert!(graph.has_edge(3, 2));
+        assert_eq!(
+            format!("{warnings:?}"),
+            "[InvalidNumber(3, \"x\"), VertexOutOfRange(5, 9, 4), SelfLoop(6), ParallelEdge(7), ExtraColumn(9, \"1 3 2 x\"), EdgeCountMismatch(6, 2)]"
+        );
+
+        // 第一行出错时宽松模式也无法继续
+        assert!(Graph::from_data_lenient("a b\n0 1", false).is_err());
+    }
 }
\ No newline at end of file
@@ -1,76 +1,68 @@
-use std::borrow::Borrow;
-use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::ops::Deref;
-use crate::graph::graph::Graph;
+use crate::graph::adj_iterable::AdjIterable;
 
-struct GraphBFS {
-    graph: RefCell<Graph>,
-    visited: RefCell<Vec<bool>>,
-    order: RefCell<Vec<usize>>,
+// 广度优先遍历，只借用图，构造的时候就完成遍历
+#[derive(Debug, Clone)]
+pub struct GraphBFS<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    order: Vec<usize>,
 }
 
-impl GraphBFS {
-    fn new(file_path: &str) -> Self {
-        let mut graph = Graph::new();
-        Graph::init_matrix(&mut graph, file_path);
-        let v_size = graph.v;
+impl<'a, G: AdjIterable> GraphBFS<'a, G> {
+    // 有向图也可以
+    pub fn new(graph: &'a G) -> Self {
+        let mut bfs = Self {
+            graph,
+            visited: vec![false; graph.v()],
+            order: vec![],
+        };
 
-        Self {
-            graph: RefCell::new(graph),
-            visited: RefCell::new(vec![false; v_size]),
-            order: RefCell::new(vec![])
-        }
-    }
-
-    fn process(&self) {
-        for v in 0..self.graph.borrow().v {
-            if !self.visited.borrow()[v] {
-                self.bfs(v)
+        for v in 0..graph.v() {
+            if !bfs.visited[v] {
+                bfs.bfs(v)
             }
         }
+        bfs
     }
 
-    fn bfs(&self, s: usize) {
+    fn bfs(&mut self, s: usize) {
         let mut queue = VecDeque::new();
 
         // 每次入队的第一个元素是传进来的顶点 v
         queue.push_back(s);
-        self.visited.borrow_mut()[s] = true;
-
-        // 只要队列不空
-        while !queue.is_empty() {
-            // 首先从队首取出元素
-            let v = queue.pop_front().unwrap();
+        self.visited[s] = true;
 
+        // 只要队列不空，首先从队首取出元素
+        while let Some(v) = queue.pop_front() {
             // 然后把取出来的元素添加到 order 中
-            self.order.borrow_mut().push(v);
+            self.order.push(v);
 
             // 再对 v 相邻的节点进行遍历
-            let g = self.graph.borrow();
-            for w in g.adj(v) {
-                if !self.visited.borrow()[*w] {
-                    queue.push_back(*w);
-                    self.visited.borrow_mut()[*w] = true;
+            for w in self.graph.adj(v) {
+                if !self.visited[w] {
+                    queue.push_back(w);
+                    self.visited[w] = true;
                 }
             }
         }
     }
 
-    fn order(&self) -> Vec<usize> {
-        self.order.borrow().clone()
+    pub fn order(&self) -> &[usize] {
+        &self.order
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn bfs_test() {
-        let bfs = GraphBFS::new("g_bfs.txt");
-        bfs.process();
+        let graph = Graph::from_file("g_bfs.txt");
+        let bfs = GraphBFS::new(&graph);
 
         println!("{:?}", bfs.order());
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,133 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::*, Result};
+
+// 在内存中构建图，不需要从文件读取。各个底层表示实现几个基本操作，校验逻辑放在这里统一处理
+pub trait GraphBuilder: AdjIterable + Sized {
+    // n 个顶点，没有边的无向图
+    fn with_vertices(n: usize) -> Self;
+
+    // n 个顶点，没有边的有向图
+    fn with_vertices_directed(n: usize) -> Self;
+
+    // 添加一个新的顶点，返回它的编号
+    fn add_vertex(&mut self) -> usize;
+
+    // 直接存储边 v-w，不做校验，由 add_edge 校验后调用
+    fn insert_edge(&mut self, v: usize, w: usize);
+
+    // 删除边 v-w，返回这条边是否存在
+    fn remove_edge(&mut self, v: usize, w: usize) -> bool;
+
+    // 添加边 v-w，和读文件一样，不允许自环边和平行边
+    fn add_edge(&mut self, v: usize, w: usize) -> Result<()> {
+        self.validate_vertex(v)?;
+        self.validate_vertex(w)?;
+
+        // 不是从文本读到的边，没有行号
+        if v == w {
+            return Err(SelfLoop(0));
+        }
+        if self.has_edge(v, w) {
+            return Err(ParallelEdge(0));
+        }
+
+        self.insert_edge(v, w);
+        Ok(())
+    }
+
+    // 添加带权的边 v-w，不带权的表示和读文件时一样忽略权值
+    fn add_weighted_edge(&mut self, v: usize, w: usize, _weight: f64) -> Result<()> {
+        self.add_edge(v, w)
+    }
+
+    // n 个顶点，由边的列表构建无向图
+    fn from_edges<I: IntoIterator<Item = (usize, usize)>>(n: usize, edges: I) -> Result<Self> {
+        let mut graph = Self::with_vertices(n);
+        for (v, w) in edges {
+            graph.add_edge(v, w)?;
+        }
+        Ok(graph)
+    }
+
+    // n 个顶点，由边的列表构建有向图
+    fn from_edges_directed<I: IntoIterator<Item = (usize, usize)>>(n: usize, edges: I) -> Result<Self> {
+        let mut graph = Self::with_vertices_directed(n);
+        for (v, w) in edges {
+            graph.add_edge(v, w)?;
+        }
+        Ok(graph)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_list::AdjList;
+    use crate::graph::adj_matrix::AdjMatrix;
+    use crate::graph::adj_set::AdjSet;
+    use crate::graph::cc::CC;
+    use crate::graph::error::MatrixError;
+    use crate::graph::graph::Graph;
+    use crate::graph::path::Path;
+    use crate::graph::weighted_graph::WeightedGraph;
+
+    // 和 g.txt 相同的图
+    const EDGES: [(usize, usize); 6] = [(0, 1), (0, 2), (1, 3), (1, 4), (2, 3), (2, 6)];
+
+    fn builder_test<G: GraphBuilder>() {
+        let mut graph = G::from_edges(7, EDGES).unwrap();
+        assert_eq!(graph.v(), 7);
+        assert_eq!(graph.e(), 6);
+        assert!(graph.has_edge(3, 1));
+
+        assert_eq!(CC::new(&graph).count_cc(), 2);
+
+        // 去掉 1-3 和 2-3 之后，3 成为孤立的顶点
+        assert!(graph.remove_edge(3, 1));
+        assert!(graph.remove_edge(2, 3));
+        assert!(!graph.remove_edge(2, 3));
+        assert!(!graph.remove_edge(0, 7));
+        assert_eq!(graph.e(), 4);
+        assert!(!graph.has_edge(1, 3));
+
+        // 新的顶点 7 和 3 相连
+        assert_eq!(graph.add_vertex(), 7);
+        graph.add_edge(7, 3).unwrap();
+        assert_eq!(graph.v(), 8);
+        assert_eq!(graph.adj(3).collect::<Vec<_>>(), vec![7]);
+
+        assert_eq!(CC::new(&graph).count_cc(), 3);
+
+        assert!(matches!(graph.add_edge(0, 0), Err(MatrixError::SelfLoop(0))));
+        assert!(matches!(graph.add_edge(0, 1), Err(MatrixError::ParallelEdge(0))));
+        assert!(matches!(graph.add_edge(0, 8), Err(MatrixError::InvalidVertexEdge(8, 8))));
+
+        let graph = G::from_edges_directed(3, [(0, 1), (1, 0), (1, 2)]).unwrap();
+        assert!(graph.is_directed());
+        assert_eq!(graph.e(), 3);
+        assert!(!graph.has_edge(2, 1));
+    }
+
+    #[test]
+    fn all_backends_test() {
+        builder_test::<Graph>();
+        builder_test::<AdjSet>();
+        builder_test::<AdjList>();
+        builder_test::<AdjMatrix>();
+        builder_test::<WeightedGraph>();
+    }
+
+    #[test]
+    fn algorithm_test() {
+        let mut graph = Graph::with_vertices(4);
+        graph.add_edge(0, 1).unwrap();
+        graph.add_edge(1, 2).unwrap();
+
+        let path = Path::new(&graph, 0, 2);
+        assert_eq!(path.path(), vec![0, 1, 2]);
+
+        let path = Path::new(&graph, 0, 3);
+        assert!(!path.is_connected());
+    }
+}
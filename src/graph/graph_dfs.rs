@@ -1,63 +1,94 @@
-use std::cell::{Ref, RefCell};
-use crate::graph::graph::Graph;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::stack::stack::Stack;
 
+// 只借用图，在构造的时候就完成遍历，之后只读取结果，可以在多个线程中共享同一张图
 #[derive(Debug, Clone)]
-struct GraphDFS {
-    // visited: Vec<bool>,
-    // order: Vec<usize>,
-    // graph: Graph,
-
-    visited: RefCell<Vec<bool>>,
-    order: RefCell<Vec<usize>>,
-    graph: RefCell<Graph>,
+pub struct GraphDFS<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    order: Vec<usize>,
+    // 后序遍历，顶点的所有相邻顶点都遍历完之后才加入
+    post_order: Vec<usize>,
 }
 
-impl GraphDFS {
-    fn new() -> Self {
-        let file_path = "g.txt";
-        let mut graph = Graph::new();
-        Graph::init_matrix(&mut graph, file_path);
+impl<'a, G: AdjIterable> GraphDFS<'a, G> {
+    // 有向图也可以
+    pub fn new(graph: &'a G) -> Self {
+        let mut dfs = Self::init(graph);
+        for v in 0..graph.v() {
+            if !dfs.visited[v] {
+                dfs.dfs(v);
+            }
+        }
+        dfs
+    }
 
-        let v = vec![false; graph.v];
+    // 用显式的栈代替递归，图很大(例如很长的路径)时不会栈溢出，遍历的顺序和递归一样
+    pub fn new_iterative(graph: &'a G) -> Self {
+        let mut dfs = Self::init(graph);
+        for v in 0..graph.v() {
+            if !dfs.visited[v] {
+                dfs.dfs_iterative(v);
+            }
+        }
+        dfs
+    }
 
+    fn init(graph: &'a G) -> Self {
         Self {
+            graph,
+            visited: vec![false; graph.v()],
+            order: vec![],
+            post_order: vec![],
+        }
+    }
 
-            // visited: v,
-            // order: vec![],
-            // graph,
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
 
-            visited: RefCell::new(v),
-            order: RefCell::new(vec![]),
-            graph: RefCell::new(graph),
-        }
+    pub fn post_order(&self) -> &[usize] {
+        &self.post_order
     }
 
-    fn process(&mut self) {
-        // self.dfs(0);
+    fn dfs(&mut self, v: usize) {
+        self.visited[v] = true;
+        self.order.push(v);
 
-        for v in 0..self.graph.borrow().v {
-            if !self.visited.borrow_mut()[v] {
-                self.dfs(v);
+        let g = self.graph;
+        for w in g.adj(v) {
+            if !self.visited[w] {
+                self.dfs(w);
             }
         }
-    }
 
-    fn order(&self) -> Vec<usize> {
-        // self.order.clone()
-        self.order.borrow().clone()
+        self.post_order.push(v);
     }
 
-    fn dfs(&self, v: usize) {
-        // self.visited[v] = true;
-        // self.order.push(v);
-
-        self.visited.borrow_mut()[v] = true;
-        self.order.borrow_mut().push(v);
-
-        let g = self.graph.borrow();
-        for w in g.adj(v) {
-            if !self.visited.borrow_mut()[*w] {
-                self.dfs(*w);
+    // 栈中存放 (顶点，还没有遍历的相邻顶点)，相当于递归时每一层的状态
+    fn dfs_iterative(&mut self, s: usize) {
+        let g = self.graph;
+        let mut stack = Stack::new();
+
+        self.visited[s] = true;
+        self.order.push(s);
+        stack.push((s, g.adj(s)));
+
+        while let Some((v, iter)) = stack.peek_mut() {
+            let v = *v;
+            match iter.next() {
+                Some(w) => {
+                    if !self.visited[w] {
+                        self.visited[w] = true;
+                        self.order.push(w);
+                        stack.push((w, g.adj(w)));
+                    }
+                }
+                // 相邻顶点都遍历完了，相当于递归返回
+                None => {
+                    self.post_order.push(v);
+                    stack.pop();
+                }
             }
         }
     }
@@ -67,13 +98,48 @@ impl GraphDFS {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::csr_graph::CsrGraph;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn dfs_test() {
-        let mut dfs = GraphDFS::new();
-
-        dfs.process();
+        let graph = Graph::from_file("g.txt");
+        let dfs = GraphDFS::new(&graph);
 
         println!("{:?}", dfs.order());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn directed_test() {
+        let graph = Graph::from_file_directed("g_directed.txt");
+        let dfs = GraphDFS::new(&graph);
+        assert_eq!(dfs.order(), vec![0, 1, 2, 4, 3]);
+
+        let rev = graph.reverse();
+        let dfs = GraphDFS::new(&rev);
+        assert_eq!(dfs.order(), vec![0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn iterative_test() {
+        for file_path in ["g.txt", "g_bfs.txt", "g_bridges.txt"] {
+            let graph = Graph::from_file(file_path);
+            let expected = GraphDFS::new(&graph);
+            let dfs = GraphDFS::new_iterative(&graph);
+
+            assert_eq!(dfs.order(), expected.order());
+            assert_eq!(dfs.post_order(), expected.post_order());
+        }
+    }
+
+    #[test]
+    fn long_path_test() {
+        // 一百万个顶点的路径，递归的 dfs 会栈溢出
+        let n = 1_000_000;
+        let graph = CsrGraph::from_edges(n, (0..n - 1).map(|v| (v, v + 1))).unwrap();
+        let dfs = GraphDFS::new_iterative(&graph);
+
+        assert!(dfs.order().iter().copied().eq(0..n));
+        assert!(dfs.post_order().iter().copied().eq((0..n).rev()));
+    }
+}
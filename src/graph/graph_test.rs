@@ -75,20 +75,19 @@ impl GraphTest {
         let mut num1 = 0;
         let mut num2 = 0;
         // 从第二行开始读取，并赋值给二维数组
-        let mut s =  s.lines().skip(1);
-        while let Some(line) = s.next() {
+        for (line_no, line) in s.lines().enumerate().skip(1) {
             let mut iter = line.split_whitespace();
             num1 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
             num2 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
 
             // 遇到平行边(将要处理的边在前面已经存储过了就是平行边)
             if adj[num1].contains(&num2) {
-                return Err(ParallelEdge);
+                return Err(ParallelEdge(line_no + 1));
             }
 
             // 遇到自环边
             if num1 == num2 {
-                return Err(SelfLoop);
+                return Err(SelfLoop(line_no + 1));
             }
 
             if let Ok(_) = self.validate_vertex(num1) {
@@ -230,21 +229,20 @@ impl Graph {
         let mut num1 = 0;
         let mut num2 = 0;
         // 从第二行开始读取，并赋值给二维数组
-        let mut s = content.lines().skip(1);
-        while let Some(line) = s.next() {
+        for (line_no, line) in content.lines().enumerate().skip(1) {
             let mut iter = line.split_whitespace();
             num1 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
             num2 = usize::from_str(iter.next().unwrap_or_default()).unwrap_or_default();
 
             // 遇到平行边(将要处理的边在前面已经存储过了就是平行边)
             if adj[num1].contains(&num2) {
-                return Err(ParallelEdge);
+                return Err(ParallelEdge(line_no + 1));
             }
 
 
             // 遇到自环边
             if num1 == num2 {
-                return Err(SelfLoop);
+                return Err(SelfLoop(line_no + 1));
             }
 
             if let Ok(_) = self.validate_vertex(num1) {
@@ -0,0 +1,334 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::TooManyVertices, Result};
+
+// 状态压缩 dp 最多支持的顶点数，dp 数组大小是 2^V * V
+pub const MAX_DP_VERTICES: usize = 20;
+
+// 哈密尔顿路径和哈密尔顿回路：每个顶点恰好经过一次
+// 只借用图。回溯和 dp 的代价都很大，所以每个查询用到时才计算
+#[derive(Debug, Clone)]
+pub struct Hamilton<'a, G> {
+    graph: &'a G,
+}
+
+impl<'a, G: AdjIterable> Hamilton<'a, G> {
+    pub fn new(graph: &'a G) -> Self {
+        Self { graph }
+    }
+
+    // 回溯法找哈密尔顿回路，从 0 出发，首尾都是 0，没有时返回空
+    pub fn hamilton_cycle(&self) -> Vec<usize> {
+        let g = self.graph;
+        if g.v() == 0 {
+            return vec![];
+        }
+
+        let mut visited = vec![false; g.v()];
+        let mut path = vec![];
+        if self.dfs(0, 0, true, &mut visited, &mut path, g.v()) {
+            path.push(0);
+            return path;
+        }
+        vec![]
+    }
+
+    // 回溯法找哈密尔顿路径，依次尝试每个顶点作为起点
+    pub fn hamilton_path(&self) -> Vec<usize> {
+        let v_size = self.graph.v();
+        for s in 0..v_size {
+            let path = self.hamilton_path_from(s);
+            if !path.is_empty() {
+                return path;
+            }
+        }
+        vec![]
+    }
+
+    // 从 s 出发的哈密尔顿路径
+    pub fn hamilton_path_from(&self, s: usize) -> Vec<usize> {
+        let g = self.graph;
+        let _ = g.validate_vertex(s);
+
+        let mut visited = vec![false; g.v()];
+        let mut path = vec![];
+        if self.dfs(s, s, false, &mut visited, &mut path, g.v()) {
+            return path;
+        }
+        vec![]
+    }
+
+    // left 是还没有访问的顶点数(包括 v)，为 0 时所有顶点都访问过了
+    fn dfs(&self, v: usize, start: usize, cycle: bool, visited: &mut [bool], path: &mut Vec<usize>, left: usize) -> bool {
+        let g = self.graph;
+
+        visited[v] = true;
+        path.push(v);
+        let left = left - 1;
+
+        if left == 0 {
+            // 找回路时，最后一个顶点还要能回到起点
+            if !cycle || g.has_edge(v, start) {
+                return true;
+            }
+        } else if !self.is_dead_end(v, start, cycle, visited, left) {
+            for w in g.adj(v) {
+                if !visited[w] && self.dfs(w, start, cycle, visited, path, left) {
+                    return true;
+                }
+            }
+        }
+
+        // 回溯
+        visited[v] = false;
+        path.pop();
+        false
+    }
+
+    // 剪枝：已经走到 v，剩下的 left 个顶点不可能都走到时返回 true
+    // 1. 从 v 出发只经过没访问的顶点，必须能到达所有没访问的顶点
+    // 2. 无向图中，没访问的顶点在 "没访问的顶点和 v" 中至少要有 2 个邻居，只有路径的终点可以只有 1 个；
+    //    找回路时终点还要回到起点，所以起点也算作邻居，并且每个顶点都要有 2 个邻居
+    fn is_dead_end(&self, v: usize, start: usize, cycle: bool, visited: &[bool], left: usize) -> bool {
+        let g = self.graph;
+
+        let mut reached = vec![false; g.v()];
+        let mut stack = vec![v];
+        let mut count = 0;
+        reached[v] = true;
+        while let Some(x) = stack.pop() {
+            for w in g.adj(x) {
+                if !visited[w] && !reached[w] {
+                    reached[w] = true;
+                    count += 1;
+                    stack.push(w);
+                }
+            }
+        }
+        if count < left {
+            return true;
+        }
+
+        if g.is_directed() {
+            return false;
+        }
+
+        let mut ends = 0;
+        for u in (0..g.v()).filter(|&u| !visited[u]) {
+            let degree = g.adj(u).filter(|&w| !visited[w] || w == v || (cycle && w == start)).count();
+            if degree < 2 {
+                ends += 1;
+                if degree == 0 || cycle || ends > 1 {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // dp[mask * V + v] 是经过 mask 中的顶点、最后停在 v 的路径条数，所有状态放在一个数组中
+    // start 为 Some(s) 时只统计从 s 出发的路径
+    fn dp(&self, start: Option<usize>) -> Result<Vec<u64>> {
+        let g = self.graph;
+        let n = g.v();
+        if n > MAX_DP_VERTICES {
+            return Err(TooManyVertices(n, MAX_DP_VERTICES));
+        }
+
+        let mut dp = vec![0u64; n << n];
+        for v in 0..n {
+            if start.is_none_or(|s| s == v) {
+                dp[(1 << v) * n + v] = 1;
+            }
+        }
+
+        for mask in 1..(1usize << n) {
+            for v in 0..n {
+                let count = dp[mask * n + v];
+                if count == 0 {
+                    continue;
+                }
+                for w in g.adj(v) {
+                    if mask & (1 << w) == 0 {
+                        dp[(mask | (1 << w)) * n + w] += count;
+                    }
+                }
+            }
+        }
+
+        Ok(dp)
+    }
+
+    // 状态压缩 dp 统计哈密尔顿路径的条数，无向图中一条路径正反两个方向只算一次
+    pub fn count_paths(&self) -> Result<u64> {
+        let dp = self.dp(None)?;
+        let g = self.graph;
+        if g.v() == 0 {
+            return Ok(0);
+        }
+
+        let full = (1 << g.v()) - 1;
+        let count: u64 = dp[full * g.v()..].iter().sum();
+        if !g.is_directed() && g.v() > 1 {
+            return Ok(count / 2);
+        }
+        Ok(count)
+    }
+
+    // 状态压缩 dp 统计哈密尔顿回路的条数，无向图中正反两个方向只算一次
+    pub fn count_cycles(&self) -> Result<u64> {
+        let dp = self.dp(Some(0))?;
+        let g = self.graph;
+        if g.v() < 2 {
+            return Ok(0);
+        }
+
+        let full = (1 << g.v()) - 1;
+        let count: u64 = (0..g.v())
+            .filter(|&v| g.has_edge(v, 0))
+            .map(|v| dp[full * g.v() + v])
+            .sum();
+        if !g.is_directed() {
+            return Ok(count / 2);
+        }
+        Ok(count)
+    }
+
+    pub fn has_hamilton_cycle_dp(&self) -> Result<bool> {
+        Ok(self.count_cycles()? > 0)
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_matrix::AdjMatrix;
+    use crate::graph::graph::Graph;
+    use crate::graph::test_util::{lcg, random_graph};
+
+    fn check_path<G: AdjIterable>(graph: &G, path: &[usize]) {
+        let mut seen = vec![false; graph.v()];
+        for &v in path {
+            assert!(!seen[v]);
+            seen[v] = true;
+        }
+        assert!(seen.iter().all(|&x| x));
+        for pair in path.windows(2) {
+            assert!(graph.has_edge(pair[0], pair[1]));
+        }
+    }
+
+    #[test]
+    fn backtracking_test() {
+        let graph = Graph::from_file("g_hamilton.txt");
+        let h = Hamilton::new(&graph);
+
+        let cycle = h.hamilton_cycle();
+        assert_eq!(cycle, vec![0, 2, 1, 3, 0]);
+        check_path(&graph, &cycle[..cycle.len() - 1]);
+
+        let path = h.hamilton_path();
+        check_path(&graph, &path);
+
+        // 2 和 3 不相邻，从 2 出发必须到 3 结束
+        let path = h.hamilton_path_from(2);
+        assert_eq!(path, vec![2, 0, 1, 3]);
+
+        // g.txt 中顶点 5 是孤立的
+        let graph = AdjMatrix::from_file("g.txt");
+        let h = Hamilton::new(&graph);
+        assert!(h.hamilton_cycle().is_empty());
+        assert!(h.hamilton_path().is_empty());
+    }
+
+    #[test]
+    fn dp_test() {
+        // 暴力枚举所有排列核对，无向图中正反两个方向各会被数一次
+        let graph = Graph::from_file("g_hamilton.txt");
+        let h = Hamilton::new(&graph);
+        let mut expected = 0;
+        let mut perm = vec![0, 1, 2, 3];
+        permutations(&mut perm, 0, &mut |p| {
+            if p.windows(2).all(|pair| graph.has_edge(pair[0], pair[1])) {
+                expected += 1;
+            }
+        });
+
+        assert_eq!(h.count_paths().unwrap(), expected / 2);
+        assert_eq!(h.count_cycles().unwrap(), 1);
+        assert!(h.has_hamilton_cycle_dp().unwrap());
+
+        let graph = Graph::from_file("g.txt");
+        let h = Hamilton::new(&graph);
+        assert_eq!(h.count_paths().unwrap(), 0);
+        assert!(!h.has_hamilton_cycle_dp().unwrap());
+    }
+
+    #[test]
+    fn directed_test() {
+        let mut graph = Graph::new_directed();
+        graph.read_data("4 5\n0 1\n1 2\n2 3\n3 0\n0 2").unwrap();
+        let h = Hamilton::new(&graph);
+
+        assert_eq!(h.hamilton_cycle(), vec![0, 1, 2, 3, 0]);
+        assert_eq!(h.count_cycles().unwrap(), 1);
+        // 0123, 1230, 2301, 3012
+        assert_eq!(h.count_paths().unwrap(), 4);
+    }
+
+    #[test]
+    fn too_many_vertices_test() {
+        let mut graph = Graph::new();
+        graph.read_data("21 1\n0 1").unwrap();
+        assert!(Hamilton::new(&graph).count_paths().is_err());
+    }
+
+    #[test]
+    fn pruning_test() {
+        // K12 上再挂 3 个度为 1 的顶点，不可能有哈密尔顿路径，不剪枝要枚举 K12 中的所有排列
+        let mut content = String::from("15 69\n");
+        for v in 0..12 {
+            for w in v + 1..12 {
+                content.push_str(&format!("{v} {w}\n"));
+            }
+        }
+        content.push_str("0 12\n1 13\n2 14");
+        let mut graph = Graph::new();
+        graph.read_data(&content).unwrap();
+
+        let h = Hamilton::new(&graph);
+        assert!(h.hamilton_path().is_empty());
+        assert!(h.hamilton_cycle().is_empty());
+    }
+
+    #[test]
+    fn compare_with_dp_test() {
+        // 随机生成的小图，回溯法(剪枝之后)和状态压缩 dp 的结论要一致
+        let mut rand = lcg(7);
+        for _ in 0..50 {
+            let n = 8;
+            let pairs = (0..n).flat_map(|v| (v + 1..n).map(move |w| (v, w)));
+            let graph = random_graph(n, pairs, 3, &mut rand);
+
+            let h = Hamilton::new(&graph);
+            let path = h.hamilton_path();
+            assert_eq!(!path.is_empty(), h.count_paths().unwrap() > 0);
+            if !path.is_empty() {
+                check_path(&graph, &path);
+            }
+            assert_eq!(!h.hamilton_cycle().is_empty(), h.has_hamilton_cycle_dp().unwrap());
+        }
+    }
+
+    fn permutations(a: &mut Vec<usize>, k: usize, f: &mut impl FnMut(&[usize])) {
+        if k == a.len() {
+            f(a);
+            return;
+        }
+        for i in k..a.len() {
+            a.swap(k, i);
+            permutations(a, k + 1, f);
+            a.swap(k, i);
+        }
+    }
+}
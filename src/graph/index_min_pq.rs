@@ -0,0 +1,154 @@
+// 索引最小堆：堆里存的是顶点编号，按照每个顶点对应的 key 排序，可以直接修改某个顶点的 key
+// Dijkstra、Prim 这种需要不断更新某个顶点距离的算法用它，就不需要每次线性扫描找最小值
+#[derive(Debug, Clone)]
+pub struct IndexMinPQ {
+    // 堆数组，pq[i] 是顶点编号
+    pq: Vec<usize>,
+    // 反向索引，qp[v] 是顶点 v 在 pq 中的位置，不在堆中为 None
+    qp: Vec<Option<usize>>,
+    // keys[v] 是顶点 v 当前的 key
+    keys: Vec<f64>,
+}
+
+impl IndexMinPQ {
+    // n 是顶点的个数，顶点编号范围 0..n
+    pub fn new(n: usize) -> Self {
+        Self {
+            pq: Vec::with_capacity(n),
+            qp: vec![None; n],
+            keys: vec![f64::INFINITY; n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pq.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pq.is_empty()
+    }
+
+    pub fn contains(&self, v: usize) -> bool {
+        self.qp[v].is_some()
+    }
+
+    pub fn key_of(&self, v: usize) -> Option<f64> {
+        self.qp[v].map(|_| self.keys[v])
+    }
+
+    // 插入顶点 v，如果已经在堆中，就更新它的 key
+    pub fn push(&mut self, v: usize, key: f64) {
+        if self.contains(v) {
+            self.change_key(v, key);
+            return;
+        }
+
+        self.keys[v] = key;
+        self.pq.push(v);
+        self.qp[v] = Some(self.pq.len() - 1);
+        self.sift_up(self.pq.len() - 1);
+    }
+
+    // 修改顶点 v 的 key，变小上浮，变大下沉
+    pub fn change_key(&mut self, v: usize, key: f64) {
+        let Some(i) = self.qp[v] else {
+            return;
+        };
+
+        let old = self.keys[v];
+        self.keys[v] = key;
+        if key < old {
+            self.sift_up(i);
+        } else {
+            self.sift_down(i);
+        }
+    }
+
+    // 取出 key 最小的顶点，同时返回它的 key
+    pub fn pop(&mut self) -> Option<(usize, f64)> {
+        if self.pq.is_empty() {
+            return None;
+        }
+
+        let last = self.pq.len() - 1;
+        self.swap(0, last);
+        let v = self.pq.pop()?;
+        self.qp[v] = None;
+        if !self.pq.is_empty() {
+            self.sift_down(0);
+        }
+
+        Some((v, self.keys[v]))
+    }
+
+    fn less(&self, i: usize, j: usize) -> bool {
+        self.keys[self.pq[i]] < self.keys[self.pq[j]]
+    }
+
+    fn swap(&mut self, i: usize, j: usize) {
+        self.pq.swap(i, j);
+        self.qp[self.pq[i]] = Some(i);
+        self.qp[self.pq[j]] = Some(j);
+    }
+
+    fn sift_up(&mut self, mut i: usize) {
+        while i > 0 {
+            let parent = (i - 1) / 2;
+            if !self.less(i, parent) {
+                break;
+            }
+            self.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut i: usize) {
+        let n = self.pq.len();
+        loop {
+            let left = 2 * i + 1;
+            if left >= n {
+                break;
+            }
+
+            // 找出两个孩子中较小的那个
+            let mut child = left;
+            if left + 1 < n && self.less(left + 1, left) {
+                child = left + 1;
+            }
+            if !self.less(child, i) {
+                break;
+            }
+            self.swap(i, child);
+            i = child;
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_test() {
+        let mut pq = IndexMinPQ::new(5);
+        pq.push(0, 5.0);
+        pq.push(1, 3.0);
+        pq.push(2, 8.0);
+        pq.push(3, 1.0);
+        assert_eq!(pq.len(), 4);
+
+        // 减小 2 的 key，增大 3 的 key
+        pq.change_key(2, 0.5);
+        pq.push(3, 9.0);
+        assert_eq!(pq.key_of(3), Some(9.0));
+        assert_eq!(pq.key_of(4), None);
+
+        let mut order = vec![];
+        while let Some((v, _)) = pq.pop() {
+            order.push(v);
+        }
+        assert_eq!(order, vec![2, 1, 0, 3]);
+        assert!(pq.is_empty());
+    }
+}
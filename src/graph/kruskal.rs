@@ -0,0 +1,93 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::DirectedGraph, Result};
+use crate::graph::spanning_tree::SpanningTree;
+use crate::graph::union_find::UnionFind;
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 把所有边按权值从小到大排序，依次加入，用并查集判断加入的边会不会形成环
+#[derive(Debug, Clone)]
+pub struct Kruskal<'a> {
+    graph: &'a WeightedGraph,
+    mst: SpanningTree,
+}
+
+impl<'a> Kruskal<'a> {
+    pub fn new(graph: &'a WeightedGraph) -> Result<Self> {
+        if graph.is_directed() {
+            return Err(DirectedGraph);
+        }
+
+        let mut kruskal = Self {
+            graph,
+            mst: SpanningTree::new(graph.v()),
+        };
+        kruskal.process();
+        Ok(kruskal)
+    }
+
+    fn process(&mut self) {
+        let g = self.graph;
+
+        // 无向图每条边存了两次，只取 v < w 的那一次
+        let mut edges = vec![];
+        for v in 0..g.v() {
+            for (w, weight) in g.adj_weighted(v) {
+                if v < w {
+                    edges.push((v, w, weight));
+                }
+            }
+        }
+        edges.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        let mut uf = UnionFind::new(g.v());
+        for (v, w, weight) in edges {
+            if uf.union(v, w) {
+                self.mst.add_edge(v, w, weight);
+            }
+        }
+    }
+
+    pub fn result(&self) -> &SpanningTree {
+        &self.mst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::cc::CC;
+    use crate::graph::graph::Graph;
+
+    #[test]
+    fn kruskal_test() {
+        let graph = WeightedGraph::from_file("g_mst.txt");
+        let kruskal = Kruskal::new(&graph).unwrap();
+
+        let mst = kruskal.result();
+        assert_eq!(mst.weight, 14.0);
+        assert_eq!(mst.edges.len(), 6);
+        assert!(!mst.is_forest());
+        assert_eq!(mst.edges[0], (1, 2, 1.0));
+    }
+
+    #[test]
+    fn forest_test() {
+        // g.txt 中顶点 5 是孤立的
+        let graph = WeightedGraph::from_file("g.txt");
+        let kruskal = Kruskal::new(&graph).unwrap();
+
+        let mst = kruskal.result();
+        assert!(mst.is_forest());
+
+        let graph = Graph::from_file("g.txt");
+        let cc = CC::new(&graph);
+        assert_eq!(mst.components as i32, cc.count_cc());
+        assert_eq!(mst.edges.len(), 5);
+        assert_eq!(mst.weight, 5.0);
+    }
+
+    #[test]
+    fn directed_test() {
+        assert!(Kruskal::new(&WeightedGraph::from_file_directed("g_mst.txt")).is_err());
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::VecDeque;
+use crate::graph::error::{MatrixError::{InvalidVertexEdge, NegativeWeight, SourceIsSink}, Result};
+use crate::graph::flow_network::{FlowNetwork, EPS};
+
+// 最大流：Edmonds-Karp 每次用 bfs 在残量图中找最短的增广路径，Dinic 先分层再一次推送多条增广路径
+// 增广会修改边上的流量，所以 MaxFlow 拥有自己的流网络，构造的时候就把最大流求出来
+#[derive(Debug, Clone)]
+pub struct MaxFlow {
+    network: FlowNetwork,
+    s: usize,
+    t: usize,
+    max_flow: f64,
+}
+
+impl MaxFlow {
+    // Edmonds-Karp
+    pub fn new(network: FlowNetwork, s: usize, t: usize) -> Result<Self> {
+        let mut mf = Self::init(network, s, t)?;
+        mf.edmonds_karp();
+        Ok(mf)
+    }
+
+    // Dinic
+    pub fn new_dinic(network: FlowNetwork, s: usize, t: usize) -> Result<Self> {
+        let mut mf = Self::init(network, s, t)?;
+        mf.dinic();
+        Ok(mf)
+    }
+
+    // 源点和汇点必须是两个不同的顶点，容量不能是负数，否则增广不会结束
+    fn init(mut network: FlowNetwork, s: usize, t: usize) -> Result<Self> {
+        for v in [s, t] {
+            if v >= network.v {
+                return Err(InvalidVertexEdge(v, network.v));
+            }
+        }
+        if s == t {
+            return Err(SourceIsSink(s));
+        }
+        // 容量就是带权图中的权值
+        if let Some(edge) = network.original_edges().find(|edge| edge.cap < 0.0) {
+            return Err(NegativeWeight(edge.from, edge.to, edge.cap));
+        }
+
+        network.reset();
+        Ok(Self {
+            network,
+            s,
+            t,
+            max_flow: 0.0,
+        })
+    }
+
+    fn edmonds_karp(&mut self) {
+        let network = &mut self.network;
+
+        loop {
+            // 和 SinglePathGraphBFS 一样用 pre 记录路径，这里记录的是到达每个顶点的边的编号
+            let mut pre: Vec<Option<usize>> = vec![None; network.v];
+            let mut visited = vec![false; network.v];
+            let mut queue = VecDeque::new();
+            queue.push_back(self.s);
+            visited[self.s] = true;
+
+            while let Some(v) = queue.pop_front() {
+                if v == self.t {
+                    break;
+                }
+                for &i in &network.adj[v] {
+                    let edge = &network.edges[i];
+                    if !visited[edge.to] && edge.residual() > EPS {
+                        visited[edge.to] = true;
+                        pre[edge.to] = Some(i);
+                        queue.push_back(edge.to);
+                    }
+                }
+            }
+
+            // 残量图中已经到达不了汇点
+            if !visited[self.t] {
+                break;
+            }
+
+            // 增广路径上最小的剩余容量
+            let mut f = f64::INFINITY;
+            let mut cur = self.t;
+            while let Some(i) = pre[cur] {
+                f = f.min(network.edges[i].residual());
+                cur = network.edges[i].from;
+            }
+
+            let mut cur = self.t;
+            while let Some(i) = pre[cur] {
+                network.push_flow(i, f);
+                cur = network.edges[i].from;
+            }
+
+            self.max_flow += f;
+        }
+    }
+
+    fn dinic(&mut self) {
+        let network = &mut self.network;
+
+        loop {
+            // bfs 分层，level[v] 是 v 到源点的距离
+            let mut level = vec![-1; network.v];
+            let mut queue = VecDeque::new();
+            level[self.s] = 0;
+            queue.push_back(self.s);
+            while let Some(v) = queue.pop_front() {
+                for &i in &network.adj[v] {
+                    let edge = &network.edges[i];
+                    if level[edge.to] < 0 && edge.residual() > EPS {
+                        level[edge.to] = level[v] + 1;
+                        queue.push_back(edge.to);
+                    }
+                }
+            }
+
+            if level[self.t] < 0 {
+                break;
+            }
+
+            // iter[v] 是 v 下一条要尝试的边，已经走不通的边不再重复尝试
+            let mut iter = vec![0; network.v];
+            loop {
+                let f = Self::dinic_dfs(network, self.s, self.t, f64::INFINITY, &level, &mut iter);
+                if f <= EPS {
+                    break;
+                }
+                self.max_flow += f;
+            }
+        }
+    }
+
+    // 只沿着层数加 1 的边往下走，返回推送到汇点的流量
+    fn dinic_dfs(network: &mut FlowNetwork, v: usize, t: usize, f: f64, level: &[i32], iter: &mut [usize]) -> f64 {
+        if v == t {
+            return f;
+        }
+
+        while iter[v] < network.adj[v].len() {
+            let i = network.adj[v][iter[v]];
+            let (to, residual) = (network.edges[i].to, network.edges[i].residual());
+            if residual > EPS && level[to] == level[v] + 1 {
+                let d = Self::dinic_dfs(network, to, t, f.min(residual), level, iter);
+                if d > EPS {
+                    network.push_flow(i, d);
+                    return d;
+                }
+            }
+            iter[v] += 1;
+        }
+
+        0.0
+    }
+
+    pub fn max_flow(&self) -> f64 {
+        self.max_flow
+    }
+
+    // 原图中边 v -> w 上的流量，没有这条边返回 0
+    pub fn flow(&self, v: usize, w: usize) -> f64 {
+        self.network
+            .original_edges()
+            .filter(|edge| edge.from == v && edge.to == w)
+            .map(|edge| edge.flow)
+            .sum()
+    }
+
+    // 原图中每条边上的流量 (v, w, 流量)
+    pub fn edge_flows(&self) -> Vec<(usize, usize, f64)> {
+        self.network
+            .original_edges()
+            .map(|edge| (edge.from, edge.to, edge.flow))
+            .collect()
+    }
+
+    // 最小割：残量图中从源点能到达的顶点是一边，其余的是另一边
+    pub fn min_cut(&self) -> (Vec<usize>, Vec<usize>) {
+        let network = &self.network;
+        let mut visited = vec![false; network.v];
+        let mut queue = VecDeque::new();
+        visited[self.s] = true;
+        queue.push_back(self.s);
+        while let Some(v) = queue.pop_front() {
+            for &i in &network.adj[v] {
+                let edge = &network.edges[i];
+                if !visited[edge.to] && edge.residual() > EPS {
+                    visited[edge.to] = true;
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+
+        (0..network.v).partition(|&v| visited[v])
+    }
+
+    // 最小割中从源点一侧指向汇点一侧的边，它们的容量和等于最大流
+    pub fn min_cut_edges(&self) -> Vec<(usize, usize)> {
+        let (source_side, _) = self.min_cut();
+        let mut in_source = vec![false; self.network.v];
+        for v in source_side {
+            in_source[v] = true;
+        }
+
+        self.network
+            .original_edges()
+            .filter(|edge| in_source[edge.from] && !in_source[edge.to])
+            .map(|edge| (edge.from, edge.to))
+            .collect()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::error::MatrixError;
+
+    fn check_flow(mf: &MaxFlow, network: &FlowNetwork, s: usize, t: usize) {
+        // 每条边的流量不超过容量，除了源点和汇点，流入等于流出
+        let mut balance = vec![0.0; network.v];
+        for (v, w, f) in mf.edge_flows() {
+            assert!(f >= 0.0);
+            balance[v] -= f;
+            balance[w] += f;
+        }
+        for (i, edge) in network.original_edges().enumerate() {
+            assert!(mf.edge_flows()[i].2 <= edge.cap);
+        }
+        for (v, &b) in balance.iter().enumerate() {
+            if v != s && v != t {
+                assert_eq!(b, 0.0);
+            }
+        }
+        assert_eq!(balance[t], mf.max_flow());
+
+        // 最大流等于最小割
+        let cut: f64 = mf.min_cut_edges().iter()
+            .map(|&(v, w)| network.original_edges().filter(|e| e.from == v && e.to == w).map(|e| e.cap).sum::<f64>())
+            .sum();
+        assert_eq!(cut, mf.max_flow());
+    }
+
+    #[test]
+    fn edmonds_karp_test() {
+        let mf = MaxFlow::new(FlowNetwork::from_file("g_flow.txt"), 0, 5).unwrap();
+
+        assert_eq!(mf.max_flow(), 12.0);
+        check_flow(&mf, &FlowNetwork::from_file("g_flow.txt"), 0, 5);
+
+        let (source_side, sink_side) = mf.min_cut();
+        assert!(source_side.contains(&0));
+        assert!(sink_side.contains(&5));
+    }
+
+    #[test]
+    fn dinic_test() {
+        let mf = MaxFlow::new_dinic(FlowNetwork::from_file("g_flow.txt"), 0, 5).unwrap();
+
+        assert_eq!(mf.max_flow(), 12.0);
+        check_flow(&mf, &FlowNetwork::from_file("g_flow.txt"), 0, 5);
+
+        let mut network = FlowNetwork::new(4);
+        network.add_edge(0, 1, 3.0);
+        network.add_edge(0, 2, 2.0);
+        network.add_edge(1, 2, 5.0);
+        network.add_edge(1, 3, 2.0);
+        network.add_edge(2, 3, 3.0);
+
+        let dinic = MaxFlow::new_dinic(network.clone(), 0, 3).unwrap().max_flow();
+        let mf = MaxFlow::new(network, 0, 3).unwrap();
+        assert_eq!(dinic, 5.0);
+        assert_eq!(mf.max_flow(), dinic);
+        assert_eq!(mf.flow(1, 3), 2.0);
+        assert_eq!(mf.flow(3, 1), 0.0);
+        assert_eq!(mf.min_cut_edges(), vec![(0, 1), (0, 2)]);
+    }
+
+    #[test]
+    fn invalid_test() {
+        let mut network = FlowNetwork::new(2);
+        network.add_edge(0, 1, 1.0);
+        assert!(matches!(MaxFlow::new(network.clone(), 0, 0), Err(MatrixError::SourceIsSink(0))));
+        assert!(matches!(MaxFlow::new_dinic(network.clone(), 0, 2), Err(MatrixError::InvalidVertexEdge(2, 2))));
+        assert!(matches!(MaxFlow::new(network.clone(), 5, 1), Err(MatrixError::InvalidVertexEdge(5, 2))));
+
+        network.add_edge(1, 0, -1.0);
+        assert!(matches!(MaxFlow::new_dinic(network, 0, 1), Err(MatrixError::NegativeWeight(1, 0, _))));
+    }
+}
@@ -1,16 +1,46 @@
-mod adj_matrix;
-mod error;
-mod adj_list;
-mod adj_set;
-mod adj_iterable;
-mod graph;
-mod graph_dfs;
-mod cc;
-mod single_source_path;
-mod path;
-mod cycle_detection;
+pub mod adj_matrix;
+pub mod error;
+pub mod adj_list;
+pub mod adj_set;
+pub mod adj_iterable;
+pub mod graph;
+pub mod graph_dfs;
+pub mod cc;
+pub mod single_source_path;
+pub mod path;
+pub mod cycle_detection;
 mod graph_test;
-mod bi_partition_detection;
-mod graph_bfs;
-mod single_path_bfs;
-mod u_s_s_s_path;
\ No newline at end of file
+pub mod bi_partition_detection;
+pub mod graph_bfs;
+pub mod single_path_bfs;
+pub mod u_s_s_s_path;
+pub mod weighted_graph;
+pub mod index_min_pq;
+pub mod dijkstra;
+pub mod bellman_ford;
+pub mod spfa;
+pub mod floyd_warshall;
+pub mod union_find;
+pub mod spanning_tree;
+pub mod kruskal;
+pub mod prim;
+pub mod directed_cycle_detection;
+pub mod topo_sort;
+pub mod scc;
+pub mod bridges;
+pub mod euler;
+pub mod hamilton;
+pub mod flow_network;
+pub mod max_flow;
+pub mod bipartite_matching;
+pub mod symbol_graph;
+pub mod graph_builder;
+pub mod dot;
+pub mod csr_graph;
+pub mod connectivity;
+pub mod multi_source_bfs;
+pub mod bidirectional_bfs;
+pub mod zero_one_bfs;
+pub mod a_star;
+#[cfg(test)]
+mod test_util;
\ No newline at end of file
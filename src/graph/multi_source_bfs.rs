@@ -0,0 +1,148 @@
+use std::collections::VecDeque;
+use crate::graph::adj_iterable::AdjIterable;
+
+// 多源 bfs：所有源点一开始就一起入队，每个顶点得到到最近的源点的距离，以及是哪个源点先到达的
+// 距离相同时，sources 中排在前面的源点优先
+#[derive(Debug, Clone)]
+pub struct MultiSourceBFS<'a, G> {
+    graph: &'a G,
+    order: Vec<usize>,
+    pre: Vec<i32>,
+    dis: Vec<i32>,
+    // source[v] 是离 v 最近的源点，-1 表示没有源点能到达 v
+    source: Vec<i32>,
+}
+
+impl<'a, G: AdjIterable> MultiSourceBFS<'a, G> {
+    // 有向图也可以
+    pub fn new(graph: &'a G, sources: &[usize]) -> Self {
+        let v_size = graph.v();
+
+        let mut bfs = Self {
+            graph,
+            order: vec![],
+            pre: vec![-1; v_size],
+            dis: vec![-1; v_size],
+            source: vec![-1; v_size],
+        };
+        bfs.bfs(sources);
+        bfs
+    }
+
+    fn bfs(&mut self, sources: &[usize]) {
+        let mut queue = VecDeque::new();
+
+        // 所有的源点距离都是 0，重复的源点只入队一次
+        for &s in sources {
+            let _ = self.graph.validate_vertex(s);
+            if self.dis[s] == -1 {
+                queue.push_back(s);
+                self.pre[s] = s as i32;
+                self.dis[s] = 0;
+                self.source[s] = s as i32;
+            }
+        }
+
+        while let Some(v) = queue.pop_front() {
+            self.order.push(v);
+
+            for w in self.graph.adj(v) {
+                if self.dis[w] == -1 {
+                    queue.push_back(w);
+                    self.pre[w] = v as i32;
+                    self.dis[w] = self.dis[v] + 1;
+                    // w 是从 v 所属的源点扩展过来的
+                    self.source[w] = self.source[v];
+                }
+            }
+        }
+    }
+
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    pub fn is_connected_to(&self, t: usize) -> bool {
+        let _ = self.graph.validate_vertex(t);
+        self.dis[t] != -1
+    }
+
+    // 到最近的源点的距离，没有源点能到达为 -1
+    pub fn dis(&self, t: usize) -> i32 {
+        let _ = self.graph.validate_vertex(t);
+        self.dis[t]
+    }
+
+    // 离 t 最近的源点
+    pub fn source_of(&self, t: usize) -> Option<usize> {
+        let _ = self.graph.validate_vertex(t);
+        (self.source[t] != -1).then_some(self.source[t] as usize)
+    }
+
+    // 从最近的源点到 t 的路径
+    pub fn path(&self, t: usize) -> Vec<usize> {
+        let mut res = vec![];
+        if !self.is_connected_to(t) {
+            return res;
+        }
+
+        let mut cur = t;
+        while self.pre[cur] != cur as i32 {
+            res.push(cur);
+            cur = self.pre[cur] as usize;
+        }
+        res.push(cur);
+
+        res.reverse();
+        res
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_matrix::AdjMatrix;
+    use crate::graph::graph::Graph;
+    use crate::graph::u_s_s_s_path::USSSPath;
+
+    #[test]
+    fn multi_source_test() {
+        let graph = Graph::from_file("g_bfs.txt");
+        let sources = [0, 4];
+        let bfs = MultiSourceBFS::new(&graph, &sources);
+
+        // 和每个源点分别做 bfs 取最小值的结果一样
+        let single: Vec<USSSPath<Graph>> = sources.iter().map(|&s| USSSPath::new(&graph, s)).collect();
+        for t in 0..graph.v() {
+            let expected = single.iter().map(|p| p.dis(t)).filter(|&d| d >= 0).min().unwrap_or(-1);
+            assert_eq!(bfs.dis(t), expected);
+
+            let s = bfs.source_of(t).unwrap();
+            assert_eq!(single[sources.iter().position(|&x| x == s).unwrap()].dis(t), expected);
+
+            let path = bfs.path(t);
+            assert_eq!(path.len() as i32, expected + 1);
+            assert_eq!(path[0], s);
+            assert_eq!(*path.last().unwrap(), t);
+        }
+        assert_eq!(bfs.source_of(4), Some(4));
+        assert_eq!(&bfs.order()[..2], &[0, 4]);
+    }
+
+    #[test]
+    fn unreachable_test() {
+        // g.txt 中顶点 5 是孤立的
+        let graph = AdjMatrix::from_file("g.txt");
+        let bfs = MultiSourceBFS::new(&graph, &[3, 6, 3]);
+        assert_eq!(bfs.dis(0), 2);
+        assert_eq!(bfs.source_of(0), Some(3));
+        assert_eq!(bfs.dis(5), -1);
+        assert_eq!(bfs.source_of(5), None);
+        assert!(bfs.path(5).is_empty());
+
+        // 没有源点时所有顶点都不可达
+        let bfs = MultiSourceBFS::new(&graph, &[]);
+        assert!((0..graph.v()).all(|t| !bfs.is_connected_to(t)));
+    }
+}
@@ -1,74 +1,66 @@
-use std::cell::{Ref, RefCell};
-use crate::graph::graph::Graph;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::stack::stack::Stack;
 
+// 从 s 到 t 的路径，只借用图，构造的时候就完成查找
 #[derive(Debug, Clone)]
-struct Path {
-    visited: RefCell<Vec<bool>>,
-    order: RefCell<Vec<usize>>,
-    graph: RefCell<Graph>,
+pub struct Path<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    order: Vec<usize>,
 
     // 源
     s: usize,
 
     // 记录源(即记录当前顶点的上一个顶点)
-    // pre: RefCell<Vec<Option<i32>>>,     // 存储每个顶点前面的顶点
-    pre: RefCell<Vec<i32>>,     // 存储每个顶点前面的顶点
+    pre: Vec<i32>,     // 存储每个顶点前面的顶点
 
     // 目标
     t: usize
 }
 
-impl Path {
-    // 传入单源的顶点
-    fn new(s: usize, t: usize) -> Self {
-        let file_path = "g.txt";
-        let mut graph = Graph::new();
-        Graph::init_matrix(&mut graph, file_path);
+impl<'a, G: AdjIterable> Path<'a, G> {
+    // 有向图也可以
+    pub fn new(graph: &'a G, s: usize, t: usize) -> Self {
+        let mut path = Self::init(graph, s, t);
+        // 只需要针对 s 这个顶点进行 dfs，即当前的联通分量，这也意味着可能不会把整个图都遍历完
+        path.dfs(s, s);      // 初始调用时，把 parent 传进去，源的 parent 是它自己
+        path
+    }
+
+    // 用显式的栈代替递归，找到的路径和 new 一样
+    pub fn new_iterative(graph: &'a G, s: usize, t: usize) -> Self {
+        let mut path = Self::init(graph, s, t);
+        path.dfs_iterative(s);
+        path
+    }
+
+    fn init(graph: &'a G, s: usize, t: usize) -> Self {
+        let v_size = graph.v();
 
         // 校验传进来的顶点源 s
         let _ = graph.validate_vertex(s);
         let _ = graph.validate_vertex(t);
 
         Self {
-            visited: RefCell::new(vec![false; graph.v]),
-            order: RefCell::new(vec![]),
-            graph: RefCell::new(graph.clone()),
+            graph,
+            visited: vec![false; v_size],
+            order: vec![],
             s,
-            // pre: RefCell::new(vec![None; graph.v]),       // 数组赋初值 None
-            pre: RefCell::new(vec![-1; graph.v]),       // 数组赋初值 None
+            pre: vec![-1; v_size],       // 数组赋初值 -1
             t
         }
     }
 
-    fn process(&mut self) {
-        // 只需要针对 s 这个顶点进行 dfs，即当前的联通分量，这也意味着可能不会把整个图都遍历完
-        // self.dfs(self.s);
-        self.dfs(self.s, self.s);      // 初始调用时，把 parent 传进去，源的 parent 是它自己
-
-        // println!();
-        // println!("----- visited  -----");
-        // for i in self.visited.borrow().iter() {
-        //     print!("{} ", i);
-        // }
-        // println!();
-        //
-        // println!("----- pre  -----");
-        // for i in self.pre.borrow().iter() {
-        //     print!("{:?} ", i);
-        // }
-    }
-
-    fn order(&self) -> Vec<usize> {
-        self.order.borrow().clone()
+    pub fn order(&self) -> &[usize] {
+        &self.order
     }
 
-    fn dfs(&self, v: usize, parent: usize) -> bool {
-        self.visited.borrow_mut()[v] = true;
-        self.order.borrow_mut().push(v);
+    fn dfs(&mut self, v: usize, parent: usize) -> bool {
+        self.visited[v] = true;
+        self.order.push(v);
 
-        // 这句必须在下面的 return 之前，因为要对 pre 先进行赋值。如果访问了，但是没有对 pre 赋值，会默认 None，导致下面出错
-        // self.pre.borrow_mut()[v] = Some(parent as i32);
-        self.pre.borrow_mut()[v] = parent as i32;
+        // 这句必须在下面的 return 之前，因为要对 pre 先进行赋值
+        self.pre[v] = parent as i32;
 
         // 如果已经找到目标顶点，那么就没有必要再去看与目标顶点相连的节点了
         // 例如 1 是目标， 从 0 到 1，到达 1 后，就返回到 0，没有必要再继续从 1 往下遍历
@@ -76,13 +68,11 @@ impl Path {
             return true
         }
 
-        let g = self.graph.borrow();
-
-
+        let g = self.graph;
         for w in g.adj(v) {
-            if !self.visited.borrow()[*w] {
+            if !self.visited[w] {
                 // 返回回到 0 后，也没有必要再去遍历其他的节点，因为已经找到了路径
-                if self.dfs(*w, v) {
+                if self.dfs(w, v) {
                     return true;
                 }
             }
@@ -92,25 +82,58 @@ impl Path {
         false
     }
 
+    // 栈中存放 (顶点，还没有遍历的相邻顶点)，访问到 t 就停止
+    fn dfs_iterative(&mut self, s: usize) {
+        self.visited[s] = true;
+        self.order.push(s);
+        self.pre[s] = s as i32;
+        if s == self.t {
+            return;
+        }
+
+        let g = self.graph;
+        let mut stack = Stack::new();
+        stack.push((s, g.adj(s)));
+
+        while let Some((v, iter)) = stack.peek_mut() {
+            let v = *v;
+            match iter.next() {
+                Some(w) => {
+                    if !self.visited[w] {
+                        self.visited[w] = true;
+                        self.order.push(w);
+                        self.pre[w] = v as i32;
+                        if w == self.t {
+                            return;
+                        }
+                        stack.push((w, g.adj(w)));
+                    }
+                }
+                None => {
+                    stack.pop();
+                }
+            }
+        }
+    }
+
     // 从源 s 到 t 是否可达
-    fn is_connected(&self) -> bool {
+    pub fn is_connected(&self) -> bool {
         // 只需看在深度遍历的过程中，t 有没有被遍历到
-        self.visited.borrow()[self.t]
+        self.visited[self.t]
     }
 
     // 从源到目标 t 的路径
-    fn path(&self) -> Vec<usize> {
+    pub fn path(&self) -> Vec<usize> {
         let mut res = Vec::new();
         if !self.is_connected() {
             return res;
         }
 
         // 从 t 开始，用 pre 数组往前查
-        let mut cur = self.t as i32;
-        while cur != self.s as i32 {
-            res.push(cur as usize);
-            // cur = self.pre.borrow()[cur as usize].unwrap_or_default();
-            cur = self.pre.borrow()[cur as usize];
+        let mut cur = self.t;
+        while cur != self.s {
+            res.push(cur);
+            cur = self.pre[cur] as usize;
         }
         res.push(self.s);
 
@@ -123,25 +146,41 @@ impl Path {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::csr_graph::CsrGraph;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn dfs_test() {
-        let mut path = Path::new(0, 6);
-        path.process();
-        println!("0 -> 6: {:?}", path.path());
+        let graph = Graph::from_file("g.txt");
 
+        let path = Path::new(&graph, 0, 6);
+        println!("0 -> 6: {:?}", path.path());
 
-        let mut path = Path::new(0, 1);
-        path.process();
+        let path = Path::new(&graph, 0, 1);
         println!("0 -> 1: {:?}", path.path());
 
-
-        let mut path = Path::new(0, 5);
-        path.process();
+        let path = Path::new(&graph, 0, 5);
         println!("0 -> 5: {:?}", path.path());
 
-        let mut path = Path::new(1, 6);
-        path.process();
+        let path = Path::new(&graph, 1, 6);
         println!("1 -> 6: {:?}", path.path());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn iterative_test() {
+        let graph = Graph::from_file("g.txt");
+        for (s, t) in [(0, 6), (0, 1), (0, 5), (1, 6)] {
+            let expected = Path::new(&graph, s, t);
+            let path = Path::new_iterative(&graph, s, t);
+
+            assert_eq!(path.order(), expected.order());
+            assert_eq!(path.path(), expected.path());
+        }
+
+        let n = 1_000_000;
+        let graph = CsrGraph::from_edges(n, (0..n - 1).map(|v| (v, v + 1))).unwrap();
+        let path = Path::new_iterative(&graph, 0, n - 1);
+        assert!(path.is_connected());
+        assert!(path.path().into_iter().eq(0..n));
+    }
+}
@@ -0,0 +1,180 @@
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::DirectedGraph, Result};
+use crate::graph::index_min_pq::IndexMinPQ;
+use crate::graph::spanning_tree::SpanningTree;
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 从一个顶点开始，每次选出连接树和树外顶点的最短的边(切分定理)
+// lazy 版本把横切边都放进堆里，取出时再判断是否失效；eager 版本堆里只存树外顶点到树的最短距离
+#[derive(Debug, Clone)]
+pub struct Prim<'a> {
+    graph: &'a WeightedGraph,
+    mst: SpanningTree,
+}
+
+// 堆中的边，按权值排序。std 的 BinaryHeap 是最大堆，所以比较时反过来
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HeapEdge {
+    v: usize,
+    w: usize,
+    weight: f64,
+}
+
+impl Eq for HeapEdge {}
+
+impl PartialOrd for HeapEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for HeapEdge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.weight.total_cmp(&self.weight)
+    }
+}
+
+impl<'a> Prim<'a> {
+    // eager Prim
+    pub fn new(graph: &'a WeightedGraph) -> Result<Self> {
+        let mut prim = Self::init(graph)?;
+        prim.process();
+        Ok(prim)
+    }
+
+    // lazy Prim，得到的生成树和 new 一样
+    pub fn new_lazy(graph: &'a WeightedGraph) -> Result<Self> {
+        let mut prim = Self::init(graph)?;
+        prim.process_lazy();
+        Ok(prim)
+    }
+
+    fn init(graph: &'a WeightedGraph) -> Result<Self> {
+        if graph.is_directed() {
+            return Err(DirectedGraph);
+        }
+
+        Ok(Self {
+            graph,
+            mst: SpanningTree::new(graph.v()),
+        })
+    }
+
+    // lazy Prim，图不联通时对每个联通分量分别求生成树
+    fn process_lazy(&mut self) {
+        let g = self.graph;
+        let mst = &mut self.mst;
+        let mut visited = vec![false; g.v()];
+        let mut heap = BinaryHeap::new();
+
+        for s in 0..g.v() {
+            if visited[s] {
+                continue;
+            }
+
+            visited[s] = true;
+            for (w, weight) in g.adj_weighted(s) {
+                heap.push(HeapEdge { v: s, w, weight });
+            }
+
+            while let Some(edge) = heap.pop() {
+                // 两个端点都在树中，这条边已经不是横切边了
+                if visited[edge.v] && visited[edge.w] {
+                    continue;
+                }
+
+                mst.add_edge(edge.v, edge.w, edge.weight);
+
+                let new_v = if visited[edge.v] { edge.w } else { edge.v };
+                visited[new_v] = true;
+                for (w, weight) in g.adj_weighted(new_v) {
+                    if !visited[w] {
+                        heap.push(HeapEdge { v: new_v, w, weight });
+                    }
+                }
+            }
+        }
+    }
+
+    // eager Prim，用索引堆维护每个树外顶点到树的最短边
+    fn process(&mut self) {
+        let g = self.graph;
+        let mst = &mut self.mst;
+        let mut visited = vec![false; g.v()];
+        // edge_to[w] 是把 w 连到树上的最短边的另一个端点
+        let mut edge_to = vec![0; g.v()];
+        let mut pq = IndexMinPQ::new(g.v());
+
+        for s in 0..g.v() {
+            if visited[s] {
+                continue;
+            }
+
+            pq.push(s, 0.0);
+            edge_to[s] = s;
+
+            while let Some((v, weight)) = pq.pop() {
+                visited[v] = true;
+                if edge_to[v] != v {
+                    mst.add_edge(edge_to[v], v, weight);
+                }
+
+                for (w, weight) in g.adj_weighted(v) {
+                    if visited[w] {
+                        continue;
+                    }
+                    if pq.key_of(w).is_none_or(|key| weight < key) {
+                        edge_to[w] = v;
+                        pq.push(w, weight);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn result(&self) -> &SpanningTree {
+        &self.mst
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::kruskal::Kruskal;
+
+    // 边的顺序和方向不影响生成树，统一成 v < w 再排序后比较
+    fn sorted_edges(mst: &SpanningTree) -> Vec<(usize, usize, f64)> {
+        let mut edges: Vec<_> = mst.edges.iter().map(|&(v, w, weight)| (v.min(w), v.max(w), weight)).collect();
+        edges.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+        edges
+    }
+
+    #[test]
+    fn prim_test() {
+        let graph = WeightedGraph::from_file("g_mst.txt");
+        let kruskal = Kruskal::new(&graph).unwrap();
+
+        let lazy = Prim::new_lazy(&graph).unwrap();
+        let eager = Prim::new(&graph).unwrap();
+
+        assert_eq!(lazy.result().weight, 14.0);
+        assert_eq!(eager.result().weight, 14.0);
+        assert_eq!(sorted_edges(lazy.result()), sorted_edges(kruskal.result()));
+        assert_eq!(sorted_edges(eager.result()), sorted_edges(kruskal.result()));
+    }
+
+    #[test]
+    fn forest_test() {
+        let graph = WeightedGraph::from_file("g.txt");
+
+        let prim = Prim::new_lazy(&graph).unwrap();
+        assert_eq!(prim.result().components, 2);
+        assert_eq!(prim.result().edges.len(), 5);
+
+        let prim = Prim::new(&graph).unwrap();
+        assert_eq!(prim.result().components, 2);
+        assert_eq!(prim.result().weight, 5.0);
+    }
+}
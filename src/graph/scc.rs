@@ -0,0 +1,229 @@
+use std::collections::BTreeSet;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::UndirectedGraph, Result};
+use crate::graph::graph::Graph;
+
+// strongly connected component，有向图的强联通分量
+// 和 CC 一样，visited 数组中记录每个顶点所属分量的编号
+#[derive(Debug, Clone)]
+pub struct SCC<'a, G> {
+    graph: &'a G,
+    visited: Vec<i32>,
+    scc_count: i32,
+}
+
+impl<'a, G: AdjIterable> SCC<'a, G> {
+    // Kosaraju：先求反图的后序遍历，再按照后序的逆序在原图上做 dfs，每次 dfs 访问到的就是一个强联通分量
+    pub fn new(graph: &'a G) -> Result<Self> {
+        let mut scc = Self::init(graph)?;
+
+        let rev = graph.reverse();
+        let mut rev_visited = vec![false; rev.v()];
+        let mut post_order = vec![];
+        for v in 0..rev.v() {
+            if !rev_visited[v] {
+                Self::post_dfs(&rev, v, &mut rev_visited, &mut post_order);
+            }
+        }
+
+        for &v in post_order.iter().rev() {
+            if scc.visited[v] == -1 {
+                scc.dfs(v, scc.scc_count);
+                scc.scc_count += 1;
+            }
+        }
+        Ok(scc)
+    }
+
+    // Tarjan：一次 dfs，low[v] 是 v 能回到的栈中最早的顶点的访问序号，low[v] == ord[v] 时 v 是分量的根
+    pub fn new_tarjan(graph: &'a G) -> Result<Self> {
+        let mut scc = Self::init(graph)?;
+
+        let v_size = graph.v();
+        let mut ord = vec![-1; v_size];
+        let mut low = vec![0; v_size];
+        let mut on_stack = vec![false; v_size];
+        let mut stack = vec![];
+        let mut cnt = 0;
+
+        for v in 0..v_size {
+            if ord[v] == -1 {
+                scc.tarjan(v, &mut ord, &mut low, &mut on_stack, &mut stack, &mut cnt);
+            }
+        }
+        Ok(scc)
+    }
+
+    fn init(graph: &'a G) -> Result<Self> {
+        if !graph.is_directed() {
+            return Err(UndirectedGraph);
+        }
+
+        Ok(Self {
+            graph,
+            visited: vec![-1; graph.v()],
+            scc_count: 0,
+        })
+    }
+
+    fn post_dfs(g: &G, v: usize, visited: &mut Vec<bool>, post_order: &mut Vec<usize>) {
+        visited[v] = true;
+        for w in g.adj(v) {
+            if !visited[w] {
+                Self::post_dfs(g, w, visited, post_order);
+            }
+        }
+        post_order.push(v);
+    }
+
+    fn dfs(&mut self, v: usize, ccid: i32) {
+        self.visited[v] = ccid;
+
+        let g = self.graph;
+        for w in g.adj(v) {
+            if self.visited[w] == -1 {
+                self.dfs(w, ccid);
+            }
+        }
+    }
+
+    fn tarjan(&mut self, v: usize, ord: &mut Vec<i32>, low: &mut Vec<i32>, on_stack: &mut Vec<bool>,
+              stack: &mut Vec<usize>, cnt: &mut i32) {
+        ord[v] = *cnt;
+        low[v] = *cnt;
+        *cnt += 1;
+        stack.push(v);
+        on_stack[v] = true;
+
+        let g = self.graph;
+        for w in g.adj(v) {
+            if ord[w] == -1 {
+                self.tarjan(w, ord, low, on_stack, stack, cnt);
+                low[v] = low[v].min(low[w]);
+            } else if on_stack[w] {
+                low[v] = low[v].min(ord[w]);
+            }
+        }
+
+        // v 是一个强联通分量的根，把栈中 v 以上的顶点都弹出来
+        if low[v] == ord[v] {
+            let ccid = self.scc_count;
+            while let Some(w) = stack.pop() {
+                on_stack[w] = false;
+                self.visited[w] = ccid;
+                if w == v {
+                    break;
+                }
+            }
+            self.scc_count += 1;
+        }
+    }
+
+    pub fn count_scc(&self) -> i32 {
+        self.scc_count
+    }
+
+    // 每个顶点所属强联通分量的编号
+    pub fn ids(&self) -> &[i32] {
+        &self.visited
+    }
+
+    pub fn is_strongly_connected(&self, v: usize, w: usize) -> bool {
+        let _ = self.graph.validate_vertex(v);
+        let _ = self.graph.validate_vertex(w);
+        self.visited[v] == self.visited[w]
+    }
+
+    // 每个强联通分量包含哪些顶点
+    pub fn components(&self) -> Vec<Vec<i32>> {
+        let mut res = vec![Vec::new(); self.scc_count as usize];
+
+        for v in 0..self.graph.v() {
+            res[self.visited[v] as usize].push(v as i32);
+        }
+
+        res
+    }
+
+    // 缩点：每个强联通分量缩成一个顶点，得到的有向图一定是 DAG
+    pub fn condensation(&self) -> Graph {
+        let count = self.scc_count as usize;
+        let mut adj: Vec<BTreeSet<usize>> = (0..count).map(|_| BTreeSet::new()).collect();
+
+        let g = self.graph;
+        let visited = &self.visited;
+        for v in 0..g.v() {
+            for w in g.adj(v) {
+                let (a, b) = (visited[v] as usize, visited[w] as usize);
+                if a != b {
+                    adj[a].insert(b);
+                }
+            }
+        }
+
+        Graph {
+            v: count,
+            e: adj.iter().map(|set| set.len()).sum(),
+            adj,
+            directed: true,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_set::AdjSet;
+    use crate::graph::topo_sort::TopoSort;
+
+    // 分量编号可能不同，排序后再比较
+    fn sorted(mut comps: Vec<Vec<i32>>) -> Vec<Vec<i32>> {
+        comps.sort();
+        comps
+    }
+
+    #[test]
+    fn kosaraju_test() {
+        let graph = Graph::from_file_directed("g_scc.txt");
+        let scc = SCC::new(&graph).unwrap();
+
+        assert_eq!(scc.count_scc(), 3);
+        assert!(scc.is_strongly_connected(0, 2));
+        assert!(!scc.is_strongly_connected(2, 3));
+        assert_eq!(sorted(scc.components()), vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]]);
+    }
+
+    #[test]
+    fn tarjan_test() {
+        let graph = AdjSet::from_file_directed("g_scc.txt");
+        let kosaraju = sorted(SCC::new(&graph).unwrap().components());
+
+        let scc = SCC::new_tarjan(&graph).unwrap();
+        assert_eq!(scc.count_scc(), 3);
+        assert_eq!(sorted(scc.components()), kosaraju);
+
+        // DAG 中每个顶点都是一个强联通分量
+        let dag = Graph::from_file_directed("g_dag.txt");
+        assert_eq!(SCC::new_tarjan(&dag).unwrap().count_scc(), 6);
+
+        // 无向图没有强联通分量的说法
+        assert!(SCC::new_tarjan(&Graph::from_file("g.txt")).is_err());
+    }
+
+    #[test]
+    fn condensation_test() {
+        let graph = Graph::from_file_directed("g_scc.txt");
+        let scc = SCC::new(&graph).unwrap();
+
+        let dag = scc.condensation();
+        assert_eq!(dag.v, 3);
+        assert_eq!(dag.e, 2);
+
+        let ids = scc.ids();
+        let (a, b, c) = (ids[0] as usize, ids[3] as usize, ids[6] as usize);
+        assert!(dag.has_edge(a, b));
+        assert!(dag.has_edge(c, b));
+        assert!(TopoSort::new_kahn(&dag).is_ok());
+    }
+}
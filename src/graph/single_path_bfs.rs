@@ -1,74 +1,66 @@
-use std::borrow::Borrow;
-use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::ops::Deref;
-use crate::graph::graph::Graph;
-
-struct SinglePathGraphBFS {
-    graph: RefCell<Graph>,
-    visited: RefCell<Vec<bool>>,
-    order: RefCell<Vec<usize>>,
-    pre: RefCell<Vec<i32>>,
+use crate::graph::adj_iterable::AdjIterable;
+
+// 用 bfs 求单源路径，只借用图，构造的时候就完成遍历
+#[derive(Debug, Clone)]
+pub struct SinglePathGraphBFS<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    order: Vec<usize>,
+    pre: Vec<i32>,
     source: usize,
 }
 
-impl SinglePathGraphBFS {
-    fn new(file_path: &str, source: usize) -> Self {
-        let mut graph = Graph::new();
-        Graph::init_matrix(&mut graph, file_path);
-        let v_size = graph.v;
+impl<'a, G: AdjIterable> SinglePathGraphBFS<'a, G> {
+    // 有向图也可以
+    pub fn new(graph: &'a G, source: usize) -> Self {
+        let v_size = graph.v();
 
-        Self {
-            graph: RefCell::new(graph),
-            visited: RefCell::new(vec![false; v_size]),
-            order: RefCell::new(vec![]),
-            pre: RefCell::new(vec![-1; v_size]),
+        let mut bfs = Self {
+            graph,
+            visited: vec![false; v_size],
+            order: vec![],
+            pre: vec![-1; v_size],
             source,
-        }
-    }
-
-    fn process(&self) {
-        self.bfs(self.source)
+        };
+        bfs.bfs(source);
+        bfs
     }
 
-    fn bfs(&self, source: usize) {
+    fn bfs(&mut self, source: usize) {
         let mut queue = VecDeque::new();
 
         // 每次入队的第一个元素是传进来的顶点 v
         queue.push_back(source);
-        self.visited.borrow_mut()[source] = true;
-        self.pre.borrow_mut()[source] = source as i32;
-
-        // 只要队列不空
-        while !queue.is_empty() {
-            // 首先从队首取出元素
-            let v = queue.pop_front().unwrap();
+        self.visited[source] = true;
+        self.pre[source] = source as i32;
 
+        // 只要队列不空，首先从队首取出元素
+        while let Some(v) = queue.pop_front() {
             // 然后把取出来的元素添加到 order 中
-            self.order.borrow_mut().push(v);
+            self.order.push(v);
 
             // 再对 v 相邻的节点进行遍历
-            let g = self.graph.borrow();
-            for w in g.adj(v) {
-                if !self.visited.borrow()[*w] {
-                    queue.push_back(*w);
-                    self.visited.borrow_mut()[*w] = true;
-                    self.pre.borrow_mut()[*w] = v as i32;
+            for w in self.graph.adj(v) {
+                if !self.visited[w] {
+                    queue.push_back(w);
+                    self.visited[w] = true;
+                    self.pre[w] = v as i32;
                 }
             }
         }
     }
 
-    fn order(&self) -> Vec<usize> {
-        self.order.borrow().clone()
+    pub fn order(&self) -> &[usize] {
+        &self.order
     }
 
-    fn is_connected_to(&self, t: usize) -> bool {
-        let _ = self.graph.borrow().validate_vertex(t);
-        self.visited.borrow()[t]
+    pub fn is_connected_to(&self, t: usize) -> bool {
+        let _ = self.graph.validate_vertex(t);
+        self.visited[t]
     }
 
-    fn path(&self, target: usize) -> Vec<usize> {
+    pub fn path(&self, target: usize) -> Vec<usize> {
         let mut res = vec![];
         if !self.is_connected_to(target) {
             return res;
@@ -77,7 +69,7 @@ impl SinglePathGraphBFS {
         let mut cur = target;
         while cur != self.source {
             res.push(cur);
-            cur = self.pre.borrow()[cur] as usize;
+            cur = self.pre[cur] as usize;
         }
         res.push(self.source);
 
@@ -89,12 +81,13 @@ impl SinglePathGraphBFS {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn bfs_test() {
-        let bfs = SinglePathGraphBFS::new("g_test.txt", 0);
-        bfs.process();
+        let graph = Graph::from_file("g_test.txt");
+        let bfs = SinglePathGraphBFS::new(&graph, 0);
 
         println!("0 -> 6: {:?}", bfs.path(6));
     }
-}
\ No newline at end of file
+}
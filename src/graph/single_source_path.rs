@@ -1,85 +1,68 @@
-use std::cell::{Ref, RefCell};
-use crate::graph::graph::Graph;
+use crate::graph::adj_iterable::AdjIterable;
 
+// 单源路径，只借用图，构造的时候就从源 s 完成遍历
 #[derive(Debug, Clone)]
-struct SingleSourcePath {
-    visited: RefCell<Vec<bool>>,
-    order: RefCell<Vec<usize>>,
-    graph: RefCell<Graph>,
+pub struct SingleSourcePath<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    order: Vec<usize>,
 
     // 源
     s: usize,
 
     // 记录源(即记录当前顶点的上一个顶点)
-    pre: RefCell<Vec<i32>>,     // 存储每个顶点前面的顶点
+    pre: Vec<i32>,     // 存储每个顶点前面的顶点
 }
 
-impl SingleSourcePath {
-    // 传入单源的顶点
-    fn new(s: usize) -> Self {
-        let file_path = "g.txt";
-        let mut graph = Graph::new();
-        Graph::init_matrix(&mut graph, file_path);
+impl<'a, G: AdjIterable> SingleSourcePath<'a, G> {
+    // 传入单源的顶点，有向图也可以
+    pub fn new(graph: &'a G, s: usize) -> Self {
+        let v_size = graph.v();
 
         // 校验传进来的顶点源 s
         let _ = graph.validate_vertex(s);
 
-        Self {
-            visited: RefCell::new(vec![false; graph.v]),
-            order: RefCell::new(vec![]),
-            graph: RefCell::new(graph.clone()),
+        let mut ss = Self {
+            graph,
+            visited: vec![false; v_size],
+            order: vec![],
             s,
-            pre: RefCell::new(vec![-1; graph.v]),       // 数组赋初值 -1
-        }
-    }
+            pre: vec![-1; v_size],       // 数组赋初值 -1
+        };
 
-    fn process(&mut self) {
         // 只需要针对 s 这个顶点进行 dfs，即当前的联通分量，这也意味着可能不会把整个图都遍历完
-        // self.dfs(self.s);
-        self.dfs(self.s, self.s)      // 初始调用时，把 parent 传进去，源的 parent 是它自己
+        ss.dfs(s, s);      // 初始调用时，把 parent 传进去，源的 parent 是它自己
+        ss
     }
 
-    fn order(&self) -> Vec<usize> {
-        self.order.borrow().clone()
+    pub fn order(&self) -> &[usize] {
+        &self.order
     }
 
-    // fn dfs(&self, v: usize) {
-    //     self.visited.borrow_mut()[v] = true;
-    //     self.order.borrow_mut().push(v);
-    //
-    //     let g = self.graph.borrow();
-    //     // 只能知道 v 的下一个顶点，不能知道 v 是从哪来的（即v的上一个顶点是谁）
-    //     for w in g.adj(v) {
-    //         if !self.visited.borrow_mut()[*w] {
-    //             self.dfs(*w);
-    //         }
-    //     }
-    // }
-
-    fn dfs(&self, v: usize, parent: usize) {
-        self.visited.borrow_mut()[v] = true;
-        self.order.borrow_mut().push(v);
-
-        let g = self.graph.borrow();
-
-        self.pre.borrow_mut()[v] = parent as i32;
+    fn dfs(&mut self, v: usize, parent: usize) {
+        self.visited[v] = true;
+        self.order.push(v);
+
+        // 只知道 v 的下一个顶点不够，还要记录 v 是从哪来的（即v的上一个顶点是谁）
+        self.pre[v] = parent as i32;
+
+        let g = self.graph;
         for w in g.adj(v) {
-            if !self.visited.borrow()[*w] {
-                self.dfs(*w, v);
+            if !self.visited[w] {
+                self.dfs(w, v);
             }
         }
     }
 
     // 从源 s 到 t 是否可达
-    fn is_connected(&self, t: usize) -> bool {
-        let _ = self.graph.borrow().validate_vertex(t);
-
+    pub fn is_connected(&self, t: usize) -> bool {
+        let _ = self.graph.validate_vertex(t);
         // 只需看在深度遍历的过程中，t 有没有被遍历到
-        self.visited.borrow()[t]
+        self.visited[t]
     }
 
     // 从源到目标 t 的路径
-    fn path(&self, t: usize) -> Vec<usize> {
+    pub fn path(&self, t: usize) -> Vec<usize> {
         let mut res = Vec::new();
         if !self.is_connected(t) {
             return res;
@@ -89,7 +72,7 @@ impl SingleSourcePath {
         let mut cur = t;
         while cur != self.s {
             res.push(cur);
-            cur = self.pre.borrow()[cur] as usize;
+            cur = self.pre[cur] as usize;
         }
         res.push(self.s);
 
@@ -103,14 +86,13 @@ impl SingleSourcePath {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn dfs_test() {
-        let mut ss = SingleSourcePath::new(0);
-
-        ss.process();
+        let graph = Graph::from_file("g.txt");
+        let ss = SingleSourcePath::new(&graph, 0);
 
-        // println!("{:?}", ss.order());
         println!("0 -> 6: {:?}", ss.path(6));
         println!("0 -> 5: {:?}", ss.path(5));
         println!("0 -> 4: {:?}", ss.path(4));
@@ -118,4 +100,4 @@ mod tests {
         println!("0 -> 2: {:?}", ss.path(2));
         println!("0 -> 1: {:?}", ss.path(1));
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,33 @@
+// 最小生成树的结果，Kruskal 和 Prim 都返回这个类型
+// 图不联通时得到的是生成森林，每个联通分量各有一棵生成树
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningTree {
+    // 树上的边 (v, w, 权值)
+    pub edges: Vec<(usize, usize, f64)>,
+    // 所有边的权值之和
+    pub weight: f64,
+    // 联通分量的个数，也就是森林中树的个数
+    pub components: usize,
+}
+
+impl SpanningTree {
+    // v 个顶点的图，还没有加入任何边时，每个顶点都是一棵树
+    pub fn new(v: usize) -> Self {
+        Self {
+            edges: vec![],
+            weight: 0.0,
+            components: v,
+        }
+    }
+
+    pub fn add_edge(&mut self, v: usize, w: usize, weight: f64) {
+        self.edges.push((v, w, weight));
+        self.weight += weight;
+        self.components -= 1;
+    }
+
+    // 是否是生成森林(原图不联通)
+    pub fn is_forest(&self) -> bool {
+        self.components > 1
+    }
+}
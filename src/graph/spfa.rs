@@ -0,0 +1,170 @@
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::bellman_ford::find_cycle;
+use crate::graph::error::Result;
+use crate::graph::weighted_graph::WeightedGraph;
+use crate::queue::queue::Queue;
+
+// Shortest Path Faster Algorithm，Bellman-Ford 的队列优化版本：只有距离变小的顶点才需要再去松弛它的邻边
+#[derive(Debug, Clone)]
+pub struct SPFA<'a> {
+    graph: &'a WeightedGraph,
+    pre: Vec<i32>,
+    source: usize,
+    dis: Vec<f64>,
+    negative_cycle: Option<Vec<usize>>,
+}
+
+impl<'a> SPFA<'a> {
+    pub fn new(graph: &'a WeightedGraph, source: usize) -> Result<Self> {
+        graph.validate_vertex(source)?;
+        let v_size = graph.v();
+
+        let mut spfa = Self {
+            graph,
+            pre: vec![-1; v_size],
+            source,
+            dis: vec![f64::INFINITY; v_size],
+            negative_cycle: None,
+        };
+        spfa.process();
+        Ok(spfa)
+    }
+
+    fn process(&mut self) {
+        let g = self.graph;
+        let (pre, dis) = (&mut self.pre, &mut self.dis);
+
+        // 每个顶点同时最多在队列中出现一次，所以容量是 V 就够了
+        let mut queue = Queue::new(g.v());
+        let mut in_queue = vec![false; g.v()];
+        // 当前最短路径经过的边数，达到 V 说明路径上有环
+        let mut edges = vec![0; g.v()];
+
+        dis[self.source] = 0.0;
+        pre[self.source] = self.source as i32;
+        let _ = queue.enqueue(self.source);
+        in_queue[self.source] = true;
+
+        while let Some(v) = queue.dequeue() {
+            in_queue[v] = false;
+
+            for (w, weight) in g.adj_weighted(v) {
+                if dis[v] + weight < dis[w] {
+                    dis[w] = dis[v] + weight;
+                    pre[w] = v as i32;
+                    edges[w] = edges[v] + 1;
+
+                    // 路径边数达到 V，看 pre 中是不是已经形成了环，没有的话继续松弛，环迟早会出现
+                    if edges[w] >= g.v() {
+                        if let Some(cycle) = find_cycle(pre, w) {
+                            self.negative_cycle = Some(cycle);
+                            return;
+                        }
+                    }
+
+                    if !in_queue[w] {
+                        let _ = queue.enqueue(w);
+                        in_queue[w] = true;
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn has_negative_cycle(&self) -> bool {
+        self.negative_cycle.is_some()
+    }
+
+    // 负权环上的顶点，例如 [1, 2, 3] 表示 1 -> 2 -> 3 -> 1
+    pub fn negative_cycle(&self) -> Option<&[usize]> {
+        self.negative_cycle.as_deref()
+    }
+
+    pub fn is_connected_to(&self, t: usize) -> bool {
+        let _ = self.graph.validate_vertex(t);
+        self.dis[t] != f64::INFINITY
+    }
+
+    pub fn dis(&self, t: usize) -> f64 {
+        let _ = self.graph.validate_vertex(t);
+        self.dis[t]
+    }
+
+    pub fn dis_to(&self) -> &[f64] {
+        &self.dis
+    }
+
+    pub fn pre(&self) -> &[i32] {
+        &self.pre
+    }
+
+    pub fn path(&self, t: usize) -> Vec<usize> {
+        let mut res = vec![];
+        if self.has_negative_cycle() || !self.is_connected_to(t) {
+            return res;
+        }
+
+        let mut cur = t;
+        while cur != self.source {
+            res.push(cur);
+            cur = self.pre[cur] as usize;
+        }
+        res.push(self.source);
+
+        res.reverse();
+        res
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::bellman_ford::BellmanFord;
+    use crate::graph::test_util::{lcg, random_edges};
+
+    #[test]
+    fn spfa_test() {
+        let graph = WeightedGraph::from_file("g_weighted.txt");
+        let spfa = SPFA::new(&graph, 0).unwrap();
+
+        assert!(!spfa.has_negative_cycle());
+        assert_eq!(spfa.dis_to(), vec![0.0, 3.0, 2.0, 5.0, 6.0]);
+        assert_eq!(spfa.path(3), vec![0, 2, 1, 3]);
+    }
+
+    #[test]
+    fn negative_cycle_test() {
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("5 5\n0 1 1\n1 2 2\n2 3 -4\n3 1 1\n3 4 1").unwrap();
+
+        let spfa = SPFA::new(&graph, 0).unwrap();
+
+        let mut cycle = spfa.negative_cycle().unwrap().to_vec();
+        // 环的起点不固定，转到从最小的顶点开始再比较
+        let start = cycle.iter().position(|&v| v == 1).unwrap();
+        cycle.rotate_left(start);
+        assert_eq!(cycle, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn compare_with_bellman_ford_test() {
+        // 生成一个大的稀疏有向图，边只从小编号指向大编号，有负权边但不会有环
+        let (v, e) = (1000, 4000);
+        let mut content = format!("{} {}\n", v, e);
+        let mut rand = lcg(42);
+        for (a, b) in random_edges(v, e, &mut rand) {
+            let weight = (rand() % 20) as i64 - 5;
+            content.push_str(&format!("{} {} {}\n", a, b, weight));
+        }
+
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data(&content).unwrap();
+
+        let bf = BellmanFord::new(&graph, 0).unwrap();
+        let spfa = SPFA::new(&graph, 0).unwrap();
+
+        assert!(!bf.has_negative_cycle());
+        assert!(!spfa.has_negative_cycle());
+        assert_eq!(bf.dis_to(), spfa.dis_to());
+    }
+}
@@ -0,0 +1,221 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::str::FromStr;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::cc::CC;
+use crate::graph::dijkstra::Dijkstra;
+use crate::graph::error::{MatrixError::*, Result};
+use crate::graph::graph_builder::GraphBuilder;
+use crate::graph::u_s_s_s_path::USSSPath;
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 符号图：顶点是任意的字符串名字，内部把名字映射成 0..V 的编号，再交给底层的图和算法处理
+// 文件没有 "V E" 这一行，每行是 name1 name2 [权值]，只有一个名字的行表示一个孤立的顶点
+#[derive(Debug, Clone)]
+pub struct SymbolGraph<G> {
+    graph: G,
+    // 名字 -> 编号
+    index: BTreeMap<String, usize>,
+    // 编号 -> 名字
+    names: Vec<String>,
+}
+
+impl<G: GraphBuilder> SymbolGraph<G> {
+    pub fn new(file_path: &str) -> Result<Self> {
+        let content = fs::read_to_string(file_path).map_err(|_| ReadFileError)?;
+        Self::from_data(content, false)
+    }
+
+    pub fn new_directed(file_path: &str) -> Result<Self> {
+        let content = fs::read_to_string(file_path).map_err(|_| ReadFileError)?;
+        Self::from_data(content, true)
+    }
+
+    pub fn from_data<S: AsRef<str>>(s: S, directed: bool) -> Result<Self> {
+        let mut index = BTreeMap::new();
+        let mut names = vec![];
+        let mut edges = vec![];
+
+        // 按第一次出现的顺序给名字编号，行号从 1 开始数
+        for (line_no, line) in s.as_ref().lines().enumerate() {
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            if tokens.is_empty() {
+                continue;
+            }
+
+            let mut ids = vec![];
+            for &name in tokens.iter().take(2) {
+                let id = *index.entry(name.to_owned()).or_insert_with(|| {
+                    names.push(name.to_owned());
+                    names.len() - 1
+                });
+                ids.push(id);
+            }
+
+            if ids.len() == 2 {
+                let weight = match tokens.get(2) {
+                    None => 1.0,
+                    Some(&token) => match f64::from_str(token) {
+                        Ok(weight) if weight.is_finite() => weight,
+                        _ => return Err(InvalidWeight(line_no + 1, token.to_owned())),
+                    },
+                };
+                if tokens.len() > 3 {
+                    return Err(ExtraColumn(line_no + 1, line.to_owned()));
+                }
+                edges.push((line_no + 1, ids[0], ids[1], weight));
+            }
+        }
+
+        // 读完才知道顶点数，再直接构建底层的图，自环边和平行边的错误带上所在的行号
+        let mut graph = if directed { G::with_vertices_directed(names.len()) } else { G::with_vertices(names.len()) };
+        for (line_no, v, w, weight) in edges {
+            graph.add_weighted_edge(v, w, weight).map_err(|err| err.at_line(line_no))?;
+        }
+
+        Ok(Self {
+            graph,
+            index,
+            names,
+        })
+    }
+
+}
+
+impl<G: AdjIterable> SymbolGraph<G> {
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.index.contains_key(name)
+    }
+
+    // 名字对应的编号
+    pub fn index_of(&self, name: &str) -> Result<usize> {
+        self.index.get(name).copied().ok_or_else(|| UnknownVertex(name.to_owned()))
+    }
+
+    // 编号对应的名字
+    pub fn name_of(&self, v: usize) -> Option<&str> {
+        self.names.get(v).map(|name| name.as_str())
+    }
+
+    // 把算法返回的顶点编号转换成名字
+    pub fn names_of(&self, vertices: &[usize]) -> Vec<String> {
+        vertices.iter().map(|&v| self.names[v].clone()).collect()
+    }
+
+    // 和 name 相邻的顶点的名字
+    pub fn adj(&self, name: &str) -> Result<Vec<&str>> {
+        let v = self.index_of(name)?;
+        Ok(self.graph.adj(v).map(|w| self.names[w].as_str()).collect())
+    }
+}
+
+impl<G: AdjIterable> SymbolGraph<G> {
+    // 无权图中 from 到 to 经过边数最少的路径，不连通返回空
+    pub fn path(&self, from: &str, to: &str) -> Result<Vec<String>> {
+        let (s, t) = (self.index_of(from)?, self.index_of(to)?);
+        let usss_path = USSSPath::new(&self.graph, s);
+        Ok(self.names_of(&usss_path.path(t)))
+    }
+
+    // from 到 to 的边数，不连通为 -1
+    pub fn dis(&self, from: &str, to: &str) -> Result<i32> {
+        let (s, t) = (self.index_of(from)?, self.index_of(to)?);
+        let usss_path = USSSPath::new(&self.graph, s);
+        Ok(usss_path.dis(t))
+    }
+
+    // 每个联通分量包含哪些顶点
+    pub fn components(&self) -> Vec<Vec<String>> {
+        let cc = CC::new(&self.graph);
+        cc.components()
+            .iter()
+            .map(|component| component.iter().map(|&v| self.names[v as usize].clone()).collect())
+            .collect()
+    }
+
+    pub fn is_connected(&self, v: &str, w: &str) -> Result<bool> {
+        let (v, w) = (self.index_of(v)?, self.index_of(w)?);
+        let cc = CC::new(&self.graph);
+        Ok(cc.is_connected(v, w))
+    }
+}
+
+impl SymbolGraph<WeightedGraph> {
+    // 带权图中 from 到 to 的最短路径和长度，不连通时长度为 INFINITY，路径为空
+    pub fn shortest_path(&self, from: &str, to: &str) -> Result<(f64, Vec<String>)> {
+        let (s, t) = (self.index_of(from)?, self.index_of(to)?);
+        let dijkstra = Dijkstra::new(&self.graph, s)?;
+        Ok((dijkstra.dis(t), self.names_of(&dijkstra.path(t))))
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::error::MatrixError;
+    use crate::graph::graph::Graph;
+
+    #[test]
+    fn symbol_graph_test() {
+        let sg = SymbolGraph::<Graph>::new("g_symbol.txt").unwrap();
+
+        assert_eq!(sg.graph().v(), 6);
+        assert_eq!(sg.graph().e(), 6);
+        assert_eq!(sg.index_of("router-c").unwrap(), 2);
+        assert_eq!(sg.name_of(5), Some("router-f"));
+        assert_eq!(sg.adj("router-b").unwrap(), vec!["router-a", "router-c", "router-d"]);
+        assert!(matches!(sg.index_of("router-x"), Err(MatrixError::UnknownVertex(_))));
+
+        assert_eq!(sg.path("router-a", "router-d").unwrap(), vec!["router-a", "router-b", "router-d"]);
+        assert_eq!(sg.dis("router-a", "router-d").unwrap(), 2);
+        assert!(sg.path("router-a", "router-f").unwrap().is_empty());
+
+        let components = sg.components();
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[1], vec!["router-f"]);
+        assert!(sg.is_connected("router-a", "router-e").unwrap());
+        assert!(!sg.is_connected("router-a", "router-f").unwrap());
+    }
+
+    #[test]
+    fn shortest_path_test() {
+        let sg = SymbolGraph::<WeightedGraph>::new("g_symbol.txt").unwrap();
+        let (dis, path) = sg.shortest_path("router-a", "router-d").unwrap();
+        assert_eq!(dis, 9.0);
+        assert_eq!(path, vec!["router-a", "router-c", "router-e", "router-d"]);
+
+        let (dis, path) = sg.shortest_path("router-a", "router-f").unwrap();
+        assert_eq!(dis, f64::INFINITY);
+        assert!(path.is_empty());
+
+        // 有向图
+        let sg = SymbolGraph::<WeightedGraph>::new_directed("g_symbol.txt").unwrap();
+        assert_eq!(sg.shortest_path("router-d", "router-a").unwrap().0, f64::INFINITY);
+    }
+
+    #[test]
+    fn invalid_data_test() {
+        match SymbolGraph::<WeightedGraph>::from_data("a b 1\n\nb c x", false) {
+            Err(MatrixError::InvalidWeight(line, token)) => {
+                assert_eq!(line, 3);
+                assert_eq!(token, "x");
+            }
+            _ => panic!("expected InvalidWeight"),
+        }
+        assert!(matches!(SymbolGraph::<Graph>::from_data("a b\n\nb a", false), Err(MatrixError::ParallelEdge(3))));
+        assert!(matches!(SymbolGraph::<Graph>::from_data("a b\nc c", false), Err(MatrixError::SelfLoop(2))));
+        match SymbolGraph::<WeightedGraph>::from_data("a b 1 zzz", false) {
+            Err(MatrixError::ExtraColumn(line, content)) => assert_eq!((line, content.as_str()), (1, "a b 1 zzz")),
+            _ => panic!("expected ExtraColumn"),
+        }
+
+        // 空的输入得到一个空图
+        let sg = SymbolGraph::<Graph>::from_data("", false).unwrap();
+        assert_eq!((sg.graph().v(), sg.graph().e()), (0, 0));
+    }
+}
@@ -0,0 +1,50 @@
+use std::collections::BTreeSet;
+use crate::graph::graph::Graph;
+use crate::graph::graph_builder::GraphBuilder;
+
+// 测试中共用的随机图和网格的生成函数
+
+// 线性同余生成伪随机数，同一个种子每次得到同样的序列，测试结果是确定的
+// 低位的周期很短，所以只返回高 31 位
+pub fn lcg(mut seed: u64) -> impl FnMut() -> u64 {
+    move || {
+        seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        seed >> 33
+    }
+}
+
+// candidates 中的每一对顶点以 1/k 的概率连一条边，得到 n 个顶点的随机无向图
+pub fn random_graph<I>(n: usize, candidates: I, k: u64, rand: &mut impl FnMut() -> u64) -> Graph
+where
+    I: IntoIterator<Item = (usize, usize)>,
+{
+    let mut graph = Graph::with_vertices(n);
+    for (v, w) in candidates {
+        if rand() % k == 0 {
+            graph.add_edge(v, w).unwrap();
+        }
+    }
+    graph
+}
+
+// n 个顶点中随机取 e 条不同的边 (v, w)，v < w，没有自环边和平行边
+pub fn random_edges(n: usize, e: usize, rand: &mut impl FnMut() -> u64) -> Vec<(usize, usize)> {
+    let mut seen = BTreeSet::new();
+    let mut edges = vec![];
+    while edges.len() < e {
+        let (a, b) = (rand() as usize % n, rand() as usize % n);
+        if a != b && seen.insert((a.min(b), a.max(b))) {
+            edges.push((a.min(b), a.max(b)));
+        }
+    }
+    edges
+}
+
+// n x n 的网格，顶点 v 在第 v / n 行第 v % n 列，每个顶点和右边、下面的顶点相连
+pub fn grid_edges(n: usize) -> impl Iterator<Item = (usize, usize)> + Clone {
+    (0..n * n).flat_map(move |v| {
+        let right = (v % n + 1 < n).then_some((v, v + 1));
+        let down = (v + n < n * n).then_some((v, v + n));
+        right.into_iter().chain(down)
+    })
+}
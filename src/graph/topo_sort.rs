@@ -0,0 +1,122 @@
+use std::collections::VecDeque;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::directed_cycle_detection::DirectedCycleDetection;
+use crate::graph::error::{MatrixError::{NotDag, UndirectedGraph}, Result};
+
+// 有向无环图的拓扑排序，图中有环时返回 NotDag，并带上找到的环
+// 排序只在构造的时候用到图，所以不需要保存图的引用
+#[derive(Debug)]
+pub struct TopoSort {
+    order: Vec<usize>,
+}
+
+impl TopoSort {
+    // 深度优先遍历的后序，反过来就是拓扑序
+    pub fn new<G: AdjIterable>(graph: &G) -> Result<Self> {
+        let cd = DirectedCycleDetection::new(graph)?;
+
+        if let Some(cycle) = cd.cycle() {
+            return Err(NotDag(cycle.to_vec()));
+        }
+
+        let mut order = cd.post_order().to_vec();
+        order.reverse();
+        Ok(Self { order })
+    }
+
+    // Kahn 算法：不断取出入度为 0 的顶点，并把它指向的顶点入度减 1
+    pub fn new_kahn<G: AdjIterable>(graph: &G) -> Result<Self> {
+        if !graph.is_directed() {
+            return Err(UndirectedGraph);
+        }
+
+        let mut in_degrees = vec![0; graph.v()];
+        for v in 0..graph.v() {
+            for w in graph.adj(v) {
+                in_degrees[w] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<usize> = (0..graph.v()).filter(|&v| in_degrees[v] == 0).collect();
+        let mut order = vec![];
+        while let Some(v) = queue.pop_front() {
+            order.push(v);
+            for w in graph.adj(v) {
+                in_degrees[w] -= 1;
+                if in_degrees[w] == 0 {
+                    queue.push_back(w);
+                }
+            }
+        }
+
+        // 还有顶点没有被取出，说明有环，再用 dfs 把环找出来
+        if order.len() < graph.v() {
+            let cd = DirectedCycleDetection::new(graph)?;
+            return Err(NotDag(cd.cycle().unwrap_or_default().to_vec()));
+        }
+
+        Ok(Self { order })
+    }
+
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::adj_list::AdjList;
+    use crate::graph::error::MatrixError;
+    use crate::graph::graph::Graph;
+
+    // 每条边 v -> w 中 v 都要排在 w 前面
+    fn check_order<G: AdjIterable>(graph: &G, order: &[usize]) {
+        assert_eq!(order.len(), graph.v());
+        let mut pos = vec![0; graph.v()];
+        for (i, &v) in order.iter().enumerate() {
+            pos[v] = i;
+        }
+        for v in 0..graph.v() {
+            for w in graph.adj(v) {
+                assert!(pos[v] < pos[w]);
+            }
+        }
+    }
+
+    #[test]
+    fn topo_test() {
+        let graph = Graph::from_file_directed("g_dag.txt");
+
+        let ts = TopoSort::new(&graph).unwrap();
+        check_order(&graph, ts.order());
+        assert_eq!(ts.order(), [5, 0, 1, 3, 2, 4]);
+
+        let ts = TopoSort::new_kahn(&graph).unwrap();
+        check_order(&graph, ts.order());
+        assert_eq!(ts.order(), [5, 0, 1, 3, 2, 4]);
+
+        let graph = AdjList::from_file_directed("g_dag.txt");
+        check_order(&graph, TopoSort::new_kahn(&graph).unwrap().order());
+    }
+
+    #[test]
+    fn not_dag_test() {
+        let mut graph = Graph::new_directed();
+        graph.read_data("4 4\n0 1\n1 2\n2 3\n3 1").unwrap();
+
+        match TopoSort::new(&graph) {
+            Err(MatrixError::NotDag(cycle)) => assert_eq!(cycle, vec![1, 2, 3]),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        match TopoSort::new_kahn(&graph) {
+            Err(MatrixError::NotDag(cycle)) => assert_eq!(cycle, vec![1, 2, 3]),
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // 无向图不能拓扑排序
+        assert!(matches!(TopoSort::new(&Graph::from_file("g.txt")), Err(MatrixError::UndirectedGraph)));
+        assert!(matches!(TopoSort::new_kahn(&Graph::from_file("g.txt")), Err(MatrixError::UndirectedGraph)));
+    }
+}
@@ -1,87 +1,73 @@
-use std::borrow::Borrow;
-use std::cell::RefCell;
 use std::collections::VecDeque;
-use std::ops::Deref;
-use crate::graph::graph::Graph;
+use crate::graph::adj_iterable::AdjIterable;
 
 // Unweighted Single Source Shortest Path
-struct USSSPath {
-    graph: RefCell<Graph>,
-    visited: RefCell<Vec<bool>>,
-    order: RefCell<Vec<usize>>,
-    pre: RefCell<Vec<i32>>,
+// 只借用图，构造的时候就完成 bfs
+#[derive(Debug, Clone)]
+pub struct USSSPath<'a, G> {
+    graph: &'a G,
+    visited: Vec<bool>,
+    order: Vec<usize>,
+    pre: Vec<i32>,
     source: usize,
-    dis: RefCell<Vec<i32>>,
+    dis: Vec<i32>,
 }
 
-impl USSSPath {
-    fn new(file_path: &str, source: usize) -> Self {
-        let mut graph = Graph::new();
-        Graph::init_matrix(&mut graph, file_path);
-        let v_size = graph.v;
-
-        Self {
-            graph: RefCell::new(graph),
-            visited: RefCell::new(vec![false; v_size]),
-            order: RefCell::new(vec![]),
-            pre: RefCell::new(vec![-1; v_size]),
-            source,
-            dis: RefCell::new(vec![-1; v_size]),
-        }
-    }
-
-    fn process(&self) {
-        self.bfs(self.source);
+impl<'a, G: AdjIterable> USSSPath<'a, G> {
+    // 有向图也可以
+    pub fn new(graph: &'a G, source: usize) -> Self {
+        let v_size = graph.v();
 
-        for i in 0..self.graph.borrow().v {
-            print!("{} ", self.dis.borrow()[i]);
-        }
-        println!();
+        let mut usss_path = Self {
+            graph,
+            visited: vec![false; v_size],
+            order: vec![],
+            pre: vec![-1; v_size],
+            source,
+            dis: vec![-1; v_size],
+        };
+        usss_path.bfs(source);
+        usss_path
     }
 
-    fn bfs(&self, s: usize) {
+    fn bfs(&mut self, s: usize) {
         let mut queue = VecDeque::new();
 
         // 每次入队的第一个元素是传进来的顶点 v
         queue.push_back(s);
-        self.visited.borrow_mut()[s] = true;
-        self.pre.borrow_mut()[s] = s as i32;
-        self.dis.borrow_mut()[s] = 0;
-
-        // 只要队列不空
-        while !queue.is_empty() {
-            // 首先从队首取出元素
-            let v = queue.pop_front().unwrap();
+        self.visited[s] = true;
+        self.pre[s] = s as i32;
+        self.dis[s] = 0;
 
+        // 只要队列不空，首先从队首取出元素
+        while let Some(v) = queue.pop_front() {
             // 然后把取出来的元素添加到 order 中
-            self.order.borrow_mut().push(v);
+            self.order.push(v);
 
             // 再对 v 相邻的节点进行遍历
-            let g = self.graph.borrow();
-            for w in g.adj(v) {
-                if !self.visited.borrow()[*w] {
-                    queue.push_back(*w);
-                    self.visited.borrow_mut()[*w] = true;
-                    self.pre.borrow_mut()[*w] = v as i32;
+            for w in self.graph.adj(v) {
+                if !self.visited[w] {
+                    queue.push_back(w);
+                    self.visited[w] = true;
+                    self.pre[w] = v as i32;
 
                     // 从源点 s 到 w 的距离是从 s 到 v 再加 1
-                    let v_dis = self.dis.borrow()[v];
-                    self.dis.borrow_mut()[*w] = v_dis + 1;
+                    self.dis[w] = self.dis[v] + 1;
                 }
             }
         }
     }
 
-    fn order(&self) -> Vec<usize> {
-        self.order.borrow().clone()
+    pub fn order(&self) -> &[usize] {
+        &self.order
     }
 
-    fn is_connected_to(&self, t: usize) -> bool {
-        let _ = self.graph.borrow().validate_vertex(t);
-        self.visited.borrow()[t]
+    pub fn is_connected_to(&self, t: usize) -> bool {
+        let _ = self.graph.validate_vertex(t);
+        self.visited[t]
     }
 
-    fn path(&self, target: usize) -> Vec<usize> {
+    pub fn path(&self, target: usize) -> Vec<usize> {
         let mut res = vec![];
         if !self.is_connected_to(target) {
             return res;
@@ -90,7 +76,7 @@ impl USSSPath {
         let mut cur = target;
         while cur != self.source {
             res.push(cur);
-            cur = self.pre.borrow()[cur] as usize;
+            cur = self.pre[cur] as usize;
         }
         res.push(self.source);
 
@@ -98,22 +84,61 @@ impl USSSPath {
         res
     }
 
-    fn dis(&self, target: usize) -> i32 {
-        let _ = self.graph.borrow().validate_vertex(target);
-        self.dis.borrow()[target]
+    // 从源点到 target 的边数，不连通为 -1
+    pub fn dis(&self, target: usize) -> i32 {
+        let _ = self.graph.validate_vertex(target);
+        self.dis[target]
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::graph::adj_list::AdjList;
+    use crate::graph::adj_matrix::AdjMatrix;
+    use crate::graph::adj_set::AdjSet;
+    use crate::graph::graph::Graph;
 
     #[test]
     fn bfs_test() {
-        let bfs = USSSPath::new("g_test.txt", 0);
-        bfs.process();
+        let graph = Graph::from_file("g_test.txt");
+        let bfs = USSSPath::new(&graph, 0);
 
         println!("0 -> 6: {:?}", bfs.path(5));
         println!("0 -> 6 dis: {:?}", bfs.dis(5));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn backends_test() {
+        let g = Graph::from_file("g_bfs.txt");
+        let m = AdjMatrix::from_file("g_bfs.txt");
+        let st = AdjSet::from_file("g_bfs.txt");
+        let graph = USSSPath::new(&g, 0);
+        let matrix = USSSPath::new(&m, 0);
+        let set = USSSPath::new(&st, 0);
+
+        for t in 0..7 {
+            assert_eq!(matrix.dis(t), graph.dis(t));
+            assert_eq!(set.dis(t), graph.dis(t));
+        }
+        assert_eq!(graph.dis(5), 3);
+        assert_eq!(matrix.path(5), vec![0, 2, 6, 5]);
+    }
+
+    #[test]
+    fn directed_test() {
+        let list = AdjList::from_file_directed("g_directed.txt");
+        let bfs = USSSPath::new(&list, 2);
+
+        // 有向图中从 2 出发只能到达 4
+        assert!(bfs.is_connected_to(4));
+        assert!(!bfs.is_connected_to(0));
+        assert_eq!(bfs.dis(1), -1);
+        assert_eq!(bfs.path(4), vec![2, 4]);
+
+        let graph = Graph::from_file_directed("g_directed.txt");
+        let bfs = USSSPath::new(&graph, 0);
+        assert_eq!(bfs.dis(4), 3);
+        assert_eq!(bfs.path(4), vec![0, 1, 2, 4]);
+    }
+}
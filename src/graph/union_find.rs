@@ -0,0 +1,115 @@
+// 并查集：按照集合大小合并(小的挂到大的下面)，查找时做路径压缩
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    // parent[i] 是 i 的父节点，根节点的父节点是自己
+    parent: Vec<usize>,
+    // size[i] 是以 i 为根的集合中元素的个数，只对根节点有意义
+    size: Vec<usize>,
+    // 集合的个数
+    count: usize,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+            count: n,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    // 集合的个数，每次成功合并减一
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // 添加一个新元素，自己单独一个集合，返回它的编号
+    pub fn push(&mut self) -> usize {
+        self.parent.push(self.parent.len());
+        self.size.push(1);
+        self.count += 1;
+        self.parent.len() - 1
+    }
+
+    // 查找 p 所在集合的根节点，顺便把路径上的节点都直接挂到根节点下面
+    pub fn find(&mut self, p: usize) -> usize {
+        let mut root = p;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut cur = p;
+        while self.parent[cur] != root {
+            let next = self.parent[cur];
+            self.parent[cur] = root;
+            cur = next;
+        }
+
+        root
+    }
+
+    pub fn is_connected(&mut self, p: usize, q: usize) -> bool {
+        self.find(p) == self.find(q)
+    }
+
+    // 合并 p 和 q 所在的集合，原来就在同一个集合中返回 false
+    pub fn union(&mut self, p: usize, q: usize) -> bool {
+        let p_root = self.find(p);
+        let q_root = self.find(q);
+        if p_root == q_root {
+            return false;
+        }
+
+        if self.size[p_root] < self.size[q_root] {
+            self.parent[p_root] = q_root;
+            self.size[q_root] += self.size[p_root];
+        } else {
+            self.parent[q_root] = p_root;
+            self.size[p_root] += self.size[q_root];
+        }
+        self.count -= 1;
+
+        true
+    }
+
+    // p 所在集合的元素个数
+    pub fn size_of(&mut self, p: usize) -> usize {
+        let root = self.find(p);
+        self.size[root]
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_test() {
+        let mut uf = UnionFind::new(6);
+        assert!(uf.union(0, 1));
+        assert!(uf.union(2, 3));
+        assert!(uf.union(1, 3));
+        assert!(!uf.union(0, 2));
+
+        assert!(uf.is_connected(0, 3));
+        assert!(!uf.is_connected(0, 4));
+        assert_eq!(uf.size_of(2), 4);
+        assert_eq!(uf.size_of(5), 1);
+        assert_eq!(uf.count(), 3);
+
+        assert_eq!(uf.push(), 6);
+        assert_eq!(uf.count(), 4);
+        assert!(uf.union(6, 5));
+        assert_eq!(uf.size_of(5), 2);
+        assert_eq!(uf.count(), 3);
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::{btree_map, BTreeMap};
+use std::io;
+use std::io::Write;
+use std::iter::Copied;
+use crate::graph::adj_iterable::{AdjIterable, Edges};
+use crate::graph::graph_builder::GraphBuilder;
+use crate::graph::error::MatrixError::{InvalidWeight, PrintMatrixError};
+use crate::graph::error::Result;
+
+// 带权图，每个顶点存 相邻顶点 -> 权值 的映射
+#[derive(Debug, Clone)]
+pub struct WeightedGraph {
+    pub v: usize,
+    pub e: usize,
+    pub adj: Vec<BTreeMap<usize, f64>>,
+    pub directed: bool,
+}
+
+impl WeightedGraph {
+    pub fn new() -> Self {
+        Self {
+            v: 0,
+            e: 0,
+            adj: Default::default(),
+            directed: false,
+        }
+    }
+
+    // 有向图
+    pub fn new_directed() -> Self {
+        Self {
+            directed: true,
+            ..WeightedGraph::new()
+        }
+    }
+
+    pub fn print_adj(&self, adj: Vec<BTreeMap<usize, f64>>) -> Result<()> {
+        if adj.is_empty() {
+            return Err(PrintMatrixError);
+        }
+
+        let mut buffer = io::BufWriter::new(io::stdout());
+
+        for (v, map) in adj.iter().enumerate() {
+            write!(buffer, "vertex:{v} --> ").map_err(|_| PrintMatrixError)?;
+            for (w, weight) in map {
+                write!(buffer, "({w}: {weight}) ").map_err(|_| PrintMatrixError)?;
+            }
+            writeln!(buffer).map_err(|_| PrintMatrixError)?;
+        }
+
+        buffer.flush().map_err(|_| PrintMatrixError)?;
+
+        Ok(())
+    }
+
+    // 边 v-w 的权值，没有这条边返回 None
+    pub fn weight(&self, v: usize, w: usize) -> Option<f64> {
+        let _ = self.validate_vertex(v);
+        let _ = self.validate_vertex(w);
+
+        self.adj[v].get(&w).copied()
+    }
+
+    // 遍历顶点 v 的相邻顶点，同时带上对应边的权值
+    pub fn adj_weighted(&self, v: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let _ = self.validate_vertex(v);
+
+        self.adj[v].iter().map(|(&w, &weight)| (w, weight))
+    }
+}
+
+impl Default for WeightedGraph {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AdjIterable for WeightedGraph {
+    type Iter<'a> = Copied<btree_map::Keys<'a, usize, f64>>;
+
+    fn v(&self) -> usize {
+        self.v
+    }
+
+    fn e(&self) -> usize {
+        self.e
+    }
+
+    fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    // 不关心权值时，只返回相邻的顶点
+    fn adj(&self, v: usize) -> Self::Iter<'_> {
+        let _ = self.validate_vertex(v);
+
+        self.adj[v].keys().copied()
+    }
+
+    fn has_edge(&self, v: usize, w: usize) -> bool {
+        let _ = self.validate_vertex(v);
+        let _ = self.validate_vertex(w);
+
+        self.adj[v].contains_key(&w)
+    }
+
+    // 把读到的边存到自己的结构里
+    fn from_edge_list(v: usize, e: usize, edges: Edges, directed: bool) -> Self {
+        let mut adj: Vec<BTreeMap<usize, f64>> = (0..v).map(|_| BTreeMap::new()).collect();
+        for (num1, num2, weight) in edges {
+            adj[num1].insert(num2, weight);
+            // 无向图两个方向都要存
+            if !directed {
+                adj[num2].insert(num1, weight);
+            }
+        }
+
+        Self {
+            v,
+            e,
+            adj,
+            directed,
+        }
+    }
+
+    // 把每条边 v -> w 存成 w -> v，权值不变
+    fn reverse(&self) -> Self {
+        if !self.directed {
+            return self.clone();
+        }
+
+        let mut adj: Vec<BTreeMap<usize, f64>> = (0..self.v).map(|_| BTreeMap::new()).collect();
+        for (v, map) in self.adj.iter().enumerate() {
+            for (&w, &weight) in map {
+                adj[w].insert(v, weight);
+            }
+        }
+
+        Self {
+            v: self.v,
+            e: self.e,
+            adj,
+            directed: self.directed,
+        }
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+}
+
+
+impl GraphBuilder for WeightedGraph {
+    fn with_vertices(n: usize) -> Self {
+        Self {
+            v: n,
+            adj: (0..n).map(|_| BTreeMap::new()).collect(),
+            ..WeightedGraph::new()
+        }
+    }
+
+    fn with_vertices_directed(n: usize) -> Self {
+        Self {
+            directed: true,
+            ..WeightedGraph::with_vertices(n)
+        }
+    }
+
+    fn add_vertex(&mut self) -> usize {
+        self.adj.push(BTreeMap::new());
+        self.v += 1;
+        self.v - 1
+    }
+
+    // 不带权值时权值为 1，和读文件时省略第三列一样
+    fn insert_edge(&mut self, v: usize, w: usize) {
+        self.adj[v].insert(w, 1.0);
+        if !self.directed {
+            self.adj[w].insert(v, 1.0);
+        }
+        self.e += 1;
+    }
+
+    // 校验和 add_edge 一样，再把权值改成 weight。和读文件一样，权值必须是有限的数
+    fn add_weighted_edge(&mut self, v: usize, w: usize, weight: f64) -> Result<()> {
+        if !weight.is_finite() {
+            return Err(InvalidWeight(0, weight.to_string()));
+        }
+        self.add_edge(v, w)?;
+        self.adj[v].insert(w, weight);
+        if !self.directed {
+            self.adj[w].insert(v, weight);
+        }
+        Ok(())
+    }
+
+    fn remove_edge(&mut self, v: usize, w: usize) -> bool {
+        if v >= self.v || w >= self.v || self.adj[v].remove(&w).is_none() {
+            return false;
+        }
+        if !self.directed {
+            self.adj[w].remove(&v);
+        }
+        self.e -= 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::error::MatrixError;
+
+    #[test]
+    fn basic_test() {
+        let graph = WeightedGraph::from_file("g_weighted.txt");
+        let _ = graph.print_adj(graph.adj.clone());
+
+        assert_eq!(graph.v(), 5);
+        assert_eq!(graph.e(), 8);
+        assert_eq!(graph.weight(0, 1), Some(4.0));
+        assert_eq!(graph.weight(1, 0), Some(4.0));
+        assert_eq!(graph.weight(0, 3), None);
+        assert_eq!(graph.adj_weighted(3).collect::<Vec<_>>(), vec![(1, 2.0), (2, 4.0), (4, 1.0)]);
+    }
+
+    #[test]
+    fn weight_format_test() {
+        // 第三列可以是小数，也可以省略
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("3 2\n0 1 2.5\n1 2").unwrap();
+        assert_eq!(graph.weight(0, 1), Some(2.5));
+        assert_eq!(graph.weight(1, 2), Some(1.0));
+        assert_eq!(graph.weight(1, 0), None);
+
+        let mut graph = WeightedGraph::new();
+        match graph.read_data("3 2\n0 1 3\n1 2 abc") {
+            Err(MatrixError::InvalidWeight(line, token)) => {
+                assert_eq!(line, 3);
+                assert_eq!(token, "abc");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn add_weighted_edge_test() {
+        let mut graph = WeightedGraph::with_vertices(3);
+        graph.add_weighted_edge(0, 1, 2.5).unwrap();
+        graph.add_weighted_edge(1, 2, -1.0).unwrap();
+
+        assert_eq!(graph.e(), 2);
+        assert_eq!(graph.weight(1, 0), Some(2.5));
+        assert_eq!(graph.weight(2, 1), Some(-1.0));
+        assert!(graph.add_weighted_edge(0, 1, 3.0).is_err());
+
+        // 非有限的权值和读文件时一样被拒绝，图保持不变
+        for weight in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(graph.add_weighted_edge(0, 2, weight), Err(MatrixError::InvalidWeight(0, _))));
+        }
+        assert_eq!(graph.e(), 2);
+        assert!(!graph.has_edge(0, 2));
+    }
+}
@@ -0,0 +1,174 @@
+use crate::deque::deque::Deque;
+use crate::graph::adj_iterable::AdjIterable;
+use crate::graph::error::{MatrixError::NotZeroOneWeight, Result};
+use crate::graph::weighted_graph::WeightedGraph;
+
+// 0-1 bfs：边的权值只有 0 和 1 时的单源最短路径，比 Dijkstra 少了堆的开销
+// 用双端队列代替堆，权值为 0 的边放到队首，权值为 1 的边放到队尾，队列中的距离始终是单调的
+// Deque 是环形缓冲区，两端入队出队都是 O(1)，总的时间复杂度是 O(V + E)
+#[derive(Debug, Clone)]
+pub struct ZeroOneBFS<'a> {
+    graph: &'a WeightedGraph,
+    source: usize,
+    visited: Vec<bool>,
+    pre: Vec<i32>,
+    dis: Vec<f64>,
+    // 出队处理过的顶点数
+    expanded: usize,
+}
+
+impl<'a> ZeroOneBFS<'a> {
+    // 图中有权值不是 0 或 1 的边时返回 NotZeroOneWeight
+    pub fn new(graph: &'a WeightedGraph, source: usize) -> Result<Self> {
+        graph.validate_vertex(source)?;
+
+        for v in 0..graph.v() {
+            for (w, weight) in graph.adj_weighted(v) {
+                if weight != 0.0 && weight != 1.0 {
+                    return Err(NotZeroOneWeight(v, w, weight));
+                }
+            }
+        }
+
+        let v_size = graph.v();
+        let mut bfs = Self {
+            graph,
+            source,
+            visited: vec![false; v_size],
+            pre: vec![-1; v_size],
+            dis: vec![f64::INFINITY; v_size],
+            expanded: 0,
+        };
+        bfs.bfs();
+        Ok(bfs)
+    }
+
+    fn bfs(&mut self) {
+        let g = self.graph;
+
+        // 每次松弛成功才入队，而每个顶点只会出队处理一次，所以入队次数不超过邻接表的总长度加一
+        let cap = 1 + (0..g.v()).map(|v| g.degree(v)).sum::<usize>();
+        let mut deque = Deque::new(cap);
+
+        self.dis[self.source] = 0.0;
+        self.pre[self.source] = self.source as i32;
+        deque.add_front(self.source).expect("deque capacity too small");
+
+        while let Some(v) = deque.remove_front() {
+            // 同一个顶点可能入队多次，第一次出队时距离就确定了
+            if self.visited[v] {
+                continue;
+            }
+            self.visited[v] = true;
+            self.expanded += 1;
+
+            for (w, weight) in g.adj_weighted(v) {
+                if !self.visited[w] && self.dis[v] + weight < self.dis[w] {
+                    self.dis[w] = self.dis[v] + weight;
+                    self.pre[w] = v as i32;
+                    let res = if weight == 0.0 {
+                        deque.add_front(w)
+                    } else {
+                        deque.add_rear(w)
+                    };
+                    res.expect("deque capacity too small");
+                }
+            }
+        }
+    }
+
+    pub fn is_connected_to(&self, t: usize) -> bool {
+        let _ = self.graph.validate_vertex(t);
+        self.visited[t]
+    }
+
+    // 从源点到 t 的最短距离，不可达时是无穷大
+    pub fn dis(&self, t: usize) -> f64 {
+        let _ = self.graph.validate_vertex(t);
+        self.dis[t]
+    }
+
+    pub fn path(&self, target: usize) -> Vec<usize> {
+        let mut res = vec![];
+        if !self.is_connected_to(target) {
+            return res;
+        }
+
+        let mut cur = target;
+        while cur != self.source {
+            res.push(cur);
+            cur = self.pre[cur] as usize;
+        }
+        res.push(self.source);
+
+        res.reverse();
+        res
+    }
+
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::dijkstra::Dijkstra;
+    use crate::graph::error::MatrixError;
+    use crate::graph::graph_builder::GraphBuilder;
+    use crate::graph::test_util::grid_edges;
+
+    // n x n 的网格，第 0 行和第 0 列是道路，走道路不花费，其它的格子每走一步花费 1
+    fn grid(n: usize) -> WeightedGraph {
+        let mut graph = WeightedGraph::with_vertices(n * n);
+        let road = |v: usize| v / n == 0 || v % n == 0;
+        for (v, w) in grid_edges(n) {
+            let weight = if road(v) && road(w) { 0.0 } else { 1.0 };
+            graph.add_weighted_edge(v, w, weight).unwrap();
+        }
+        graph
+    }
+
+    #[test]
+    fn zero_one_bfs_test() {
+        let n = 20;
+        let graph = grid(n);
+        let bfs = ZeroOneBFS::new(&graph, 0).unwrap();
+
+        // 沿着道路走到右上角和左下角都不花费
+        assert_eq!(bfs.dis(n - 1), 0.0);
+        assert_eq!(bfs.dis(n * (n - 1)), 0.0);
+        assert_eq!(bfs.dis(n * n - 1), (n - 1) as f64);
+        assert_eq!(bfs.expanded(), n * n);
+
+        let dijkstra = Dijkstra::new(&graph, 0).unwrap();
+        for t in 0..n * n {
+            assert_eq!(bfs.dis(t), dijkstra.dis(t));
+
+            // 路径上的权值之和就是最短距离
+            let path = bfs.path(t);
+            let cost: f64 = path.windows(2).map(|pair| graph.weight(pair[0], pair[1]).unwrap()).sum();
+            assert_eq!(cost, bfs.dis(t));
+        }
+    }
+
+    #[test]
+    fn unreachable_test() {
+        let mut graph = WeightedGraph::new_directed();
+        graph.read_data("4 3\n0 1 0\n1 2 1\n3 0 1").unwrap();
+
+        let bfs = ZeroOneBFS::new(&graph, 0).unwrap();
+        assert_eq!(bfs.dis(2), 1.0);
+        assert_eq!(bfs.path(2), vec![0, 1, 2]);
+        assert!(!bfs.is_connected_to(3));
+        assert_eq!(bfs.dis(3), f64::INFINITY);
+        assert!(bfs.path(3).is_empty());
+    }
+
+    #[test]
+    fn invalid_weight_test() {
+        let graph = WeightedGraph::from_file("g_weighted.txt");
+        assert!(matches!(ZeroOneBFS::new(&graph, 0), Err(MatrixError::NotZeroOneWeight(..))));
+    }
+}
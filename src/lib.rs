@@ -10,4 +10,4 @@ mod hash_search;
 mod sort;
 mod tree;
 mod pra;
-mod graph;
\ No newline at end of file
+pub mod graph;
\ No newline at end of file